@@ -0,0 +1,55 @@
+use crate::error::{Error, ErrorKind, Result};
+use reqwest::{Client as ReqwestClient, Proxy, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+
+// Common parts of API clients for hosting services. Each client adds its own authentication to
+// requests. Parsing response bodies is separated from sending requests so that it can be tested
+// without network.
+
+pub fn build_client(https_proxy: &Option<impl AsRef<str>>) -> Result<ReqwestClient> {
+    let mut b = ReqwestClient::builder().user_agent("git-brws");
+
+    if let Some(p) = https_proxy {
+        let p = p.as_ref();
+        if !p.is_empty() {
+            b = b.proxy(Proxy::https(p)?);
+        }
+    }
+
+    Ok(b.build()?)
+}
+
+// Note: Endpoint is accessed via HTTPS unless it explicitly starts with 'http://'
+pub fn split_scheme(endpoint: &str) -> (&'static str, &str) {
+    match endpoint.strip_prefix("http://") {
+        Some(endpoint) => ("http", endpoint),
+        None => (
+            "https",
+            endpoint.strip_prefix("https://").unwrap_or(endpoint),
+        ),
+    }
+}
+
+// Returns body of the response. Response with non-OK status is an error.
+pub async fn send(req: RequestBuilder, service: &'static str) -> Result<String> {
+    let res = req.send().await?;
+    let status = res.status();
+    if status == StatusCode::OK {
+        Ok(res.text().await?)
+    } else {
+        Error::err(ErrorKind::ApiStatusFailure {
+            service,
+            status,
+            msg: res.text().await.unwrap_or_default(),
+        })
+    }
+}
+
+pub fn parse_json<T: DeserializeOwned>(body: &str, service: &'static str) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| {
+        Error::new(ErrorKind::ApiResponseParseError {
+            service,
+            msg: format!("{}", e),
+        })
+    })
+}
@@ -0,0 +1,102 @@
+use crate::api;
+use crate::error::Result;
+use reqwest::header;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use serde_derive::Deserialize;
+
+const API_VERSION: &str = "7.0";
+const SERVICE: &str = "Azure DevOps";
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    #[serde(rename = "pullRequestId")]
+    id: u64,
+}
+#[derive(Debug, Deserialize)]
+struct PullRequests {
+    value: Vec<PullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repo {
+    #[serde(rename = "defaultBranch")]
+    default_branch: Option<String>,
+}
+
+pub fn parse_pr_id(body: &str) -> Result<Option<u64>> {
+    let pulls: PullRequests = api::parse_json(body, SERVICE)?;
+    Ok(pulls.value.first().map(|pr| pr.id))
+}
+
+// Note: Returns branch name without 'refs/heads/'. Empty repository has no default branch.
+pub fn parse_default_branch(body: &str) -> Result<Option<String>> {
+    let repo: Repo = api::parse_json(body, SERVICE)?;
+    Ok(repo
+        .default_branch
+        .map(|b| match b.strip_prefix("refs/heads/") {
+            Some(b) => b.to_string(),
+            None => b,
+        }))
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    token: Option<&'a str>,
+    scheme: &'static str,
+    endpoint: &'a str,
+}
+
+impl<'a> Client<'a> {
+    // Note: `endpoint` is '{host}/{organization}/{project}'
+    pub fn build(
+        endpoint: &'a str,
+        token: &'a Option<impl AsRef<str>>,
+        https_proxy: &Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let (scheme, endpoint) = api::split_scheme(endpoint);
+        Ok(Self {
+            client: api::build_client(https_proxy)?,
+            token: token.as_ref().map(AsRef::as_ref),
+            scheme,
+            endpoint,
+        })
+    }
+
+    pub async fn send(&self, mut req: RequestBuilder) -> Result<String> {
+        req = req
+            .header(header::ACCEPT, "application/json")
+            .query(&[("api-version", API_VERSION)]);
+        // Personal access token is sent via basic authentication with empty user name:
+        //   https://learn.microsoft.com/en-us/azure/devops/organizations/accounts/use-personal-access-tokens-to-authenticate
+        if let Some(token) = &self.token {
+            req = req.basic_auth("", Some(token));
+        }
+        api::send(req, SERVICE).await
+    }
+
+    pub async fn find_pr_id(&self, branch: &str, repo: &str) -> Result<Option<u64>> {
+        let source = format!("refs/heads/{}", branch);
+        let params = [
+            ("searchCriteria.sourceRefName", source.as_str()),
+            ("searchCriteria.status", "active"),
+            ("$top", "1"),
+        ];
+        let url = format!(
+            "{}://{}/_apis/git/repositories/{}/pullrequests",
+            self.scheme, self.endpoint, repo,
+        );
+        let req = self.client.get(url.as_str()).query(&params);
+        let body = self.send(req).await?;
+        parse_pr_id(&body)
+    }
+
+    pub async fn default_branch(&self, repo: &str) -> Result<Option<String>> {
+        let url = format!(
+            "{}://{}/_apis/git/repositories/{}",
+            self.scheme, self.endpoint, repo,
+        );
+        let req = self.client.get(url.as_str());
+        let body = self.send(req).await?;
+        parse_default_branch(&body)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::api;
+use crate::error::Result;
+use reqwest::header;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use serde_derive::Deserialize;
+
+const SERVICE: &str = "Bitbucket";
+
+#[derive(Debug, Deserialize)]
+pub struct ParentRepo {
+    pub full_name: String,
+}
+#[derive(Debug, Deserialize)]
+pub struct Repo {
+    pub parent: Option<ParentRepo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+#[derive(Debug, Deserialize)]
+struct Links {
+    html: Link,
+}
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    links: Links,
+}
+#[derive(Debug, Deserialize)]
+struct PullRequests {
+    values: Vec<PullRequest>,
+}
+
+// Note: Pull requests are already filtered and sorted by the query. The first one is the latest.
+pub fn parse_pr_url(body: &str) -> Result<Option<String>> {
+    let pulls: PullRequests = api::parse_json(body, SERVICE)?;
+    Ok(pulls.values.into_iter().next().map(|pr| pr.links.html.href))
+}
+
+enum Credential<'a> {
+    AppPassword(&'a str, &'a str),
+    AccessToken(&'a str),
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    credential: Option<Credential<'a>>,
+    scheme: &'static str,
+    endpoint: &'a str,
+}
+
+impl<'a> Client<'a> {
+    // Note: Token is '{username}:{app password}' for app password, or access token
+    pub fn build(
+        endpoint: &'a str,
+        token: &'a Option<impl AsRef<str>>,
+        https_proxy: &Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let credential = token.as_ref().map(|t| match t.as_ref().split_once(':') {
+            Some((user, password)) => Credential::AppPassword(user, password),
+            None => Credential::AccessToken(t.as_ref()),
+        });
+        let (scheme, endpoint) = api::split_scheme(endpoint);
+
+        Ok(Self {
+            client: api::build_client(https_proxy)?,
+            credential,
+            scheme,
+            endpoint,
+        })
+    }
+
+    pub async fn send(&self, mut req: RequestBuilder) -> Result<String> {
+        req = req.header(header::ACCEPT, "application/json");
+        // https://developer.atlassian.com/cloud/bitbucket/rest/intro/#authentication
+        match &self.credential {
+            Some(Credential::AppPassword(user, password)) => {
+                req = req.basic_auth(user, Some(password));
+            }
+            Some(Credential::AccessToken(token)) => req = req.bearer_auth(token),
+            None => {}
+        }
+        api::send(req, SERVICE).await
+    }
+
+    // Note: When `source_repo` is set, only pull requests sent from the repository are matched.
+    // It is a full name like '{owner}/{repo}'.
+    pub async fn find_pr_url(
+        &self,
+        branch: &str,
+        owner: &str,
+        repo: &str,
+        source_repo: Option<&str>,
+    ) -> Result<Option<String>> {
+        // https://developer.atlassian.com/cloud/bitbucket/rest/intro/#filtering
+        let mut query = format!(r#"source.branch.name="{}" AND state="OPEN""#, branch);
+        if let Some(source) = source_repo {
+            query.push_str(&format!(r#" AND source.repository.full_name="{}""#, source));
+        }
+        let params = [("q", query.as_str()), ("sort", "-updated_on")];
+        let url = format!(
+            "{}://{}/repositories/{}/{}/pullrequests",
+            self.scheme, self.endpoint, owner, repo,
+        );
+        let req = self.client.get(url.as_str()).query(&params);
+        let body = self.send(req).await?;
+        parse_pr_url(&body)
+    }
+
+    pub async fn repo(&self, owner: &str, repo: &str) -> Result<Repo> {
+        let url = format!(
+            "{}://{}/repositories/{}/{}",
+            self.scheme, self.endpoint, owner, repo
+        );
+        let req = self.client.get(url.as_str());
+        let body = self.send(req).await?;
+        api::parse_json(&body, SERVICE)
+    }
+}
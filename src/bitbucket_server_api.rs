@@ -0,0 +1,92 @@
+use crate::api;
+use crate::error::Result;
+use reqwest::header;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use serde_derive::Deserialize;
+
+const SERVICE: &str = "Bitbucket Server";
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+#[derive(Debug, Deserialize)]
+struct Links {
+    #[serde(rename = "self")]
+    self_links: Vec<Link>,
+}
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    links: Links,
+}
+#[derive(Debug, Deserialize)]
+struct PullRequests {
+    values: Vec<PullRequest>,
+}
+
+// Note: Web page of the pull request is the first link in 'self' links
+pub fn parse_pr_url(body: &str) -> Result<Option<String>> {
+    let pulls: PullRequests = api::parse_json(body, SERVICE)?;
+    Ok(pulls
+        .values
+        .into_iter()
+        .next()
+        .and_then(|pr| pr.links.self_links.into_iter().next())
+        .map(|link| link.href))
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    token: Option<&'a str>,
+    scheme: &'static str,
+    endpoint: &'a str,
+}
+
+impl<'a> Client<'a> {
+    pub fn build(
+        endpoint: &'a str,
+        token: &'a Option<impl AsRef<str>>,
+        https_proxy: &Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let (scheme, endpoint) = api::split_scheme(endpoint);
+        Ok(Self {
+            client: api::build_client(https_proxy)?,
+            token: token.as_ref().map(AsRef::as_ref),
+            scheme,
+            endpoint,
+        })
+    }
+
+    pub async fn send(&self, mut req: RequestBuilder) -> Result<String> {
+        req = req.header(header::ACCEPT, "application/json");
+        // Personal access tokens and HTTP access tokens are sent as bearer tokens:
+        //   https://confluence.atlassian.com/bitbucketserver/http-access-tokens-939515499.html
+        if let Some(token) = &self.token {
+            req = req.bearer_auth(token);
+        }
+        api::send(req, SERVICE).await
+    }
+
+    // Note: `project` is a project key like 'PROJ' or a personal project like '~user'
+    pub async fn find_pr_url(
+        &self,
+        branch: &str,
+        project: &str,
+        repo: &str,
+    ) -> Result<Option<String>> {
+        let at = format!("refs/heads/{}", branch);
+        let params = [
+            ("direction", "OUTGOING"),
+            ("state", "OPEN"),
+            ("at", at.as_str()),
+            ("limit", "1"),
+        ];
+        let url = format!(
+            "{}://{}/projects/{}/repos/{}/pull-requests",
+            self.scheme, self.endpoint, project, repo,
+        );
+        let req = self.client.get(url.as_str()).query(&params);
+        let body = self.send(req).await?;
+        parse_pr_url(&body)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::credential;
 use std::ffi::OsString;
 use std::fmt;
 use std::io;
@@ -41,7 +42,7 @@ impl Error {
     pub fn eprintln(&self) {
         use std::error::Error;
         fn eprint_cause(e: &dyn std::error::Error) {
-            eprint!(": {}", e);
+            eprint!(": {}", credential::redact(&e.to_string()));
             if let Some(s) = e.source() {
                 eprint_cause(s);
             }
@@ -86,7 +87,40 @@ pub enum ErrorKind {
     },
     GitLabDiffNotSupported,
     BitbucketDiffNotSupported,
-    AzureDevOpsNotSupported,
+    SourceHutDiffNotSupported,
+    BitbucketServerNotSupported {
+        feature: &'static str,
+    },
+    AzureDevOpsNotSupported {
+        feature: &'static str,
+    },
+    CodeCommitNotSupported {
+        feature: &'static str,
+    },
+    GerritNotSupported {
+        feature: &'static str,
+    },
+    CgitNotSupported {
+        feature: &'static str,
+    },
+    GitWebNotSupported {
+        feature: &'static str,
+    },
+    PhabricatorNotSupported {
+        feature: &'static str,
+    },
+    PagureNotSupported {
+        feature: &'static str,
+    },
+    LaunchpadNotSupported {
+        feature: &'static str,
+    },
+    GistNotSupported {
+        feature: &'static str,
+    },
+    GitHubWikiNotSupported {
+        feature: &'static str,
+    },
     NoUserInPath {
         path: String,
     },
@@ -107,6 +141,19 @@ pub enum ErrorKind {
         status: reqwest::StatusCode,
         msg: String,
     },
+    ApiStatusFailure {
+        service: &'static str,
+        status: reqwest::StatusCode,
+        msg: String,
+    },
+    ApiResponseParseError {
+        service: &'static str,
+        msg: String,
+    },
+    InvalidToken {
+        service: &'static str,
+        expected: &'static str,
+    },
     HttpClientError(reqwest::Error),
     IoError(io::Error),
     GitCommandError {
@@ -114,6 +161,9 @@ pub enum ErrorKind {
         args: Vec<OsString>,
     },
     UnexpectedRemoteName(String),
+    LocalRemoteLoop {
+        paths: Vec<PathBuf>,
+    },
     GitObjectNotFound {
         kind: &'static str,
         object: String,
@@ -143,6 +193,22 @@ pub enum ErrorKind {
     InvalidIssueNumberFormat,
     LineSpecifiedForDir(PathBuf),
     EnvLoadError(envy::Error),
+    ConfigFileError {
+        path: PathBuf,
+        msg: String,
+    },
+    CacheFileError {
+        path: PathBuf,
+        msg: String,
+    },
+    UrlTemplateNotFound {
+        host: String,
+        template: &'static str,
+    },
+    BrokenUrlTemplate {
+        template: String,
+        msg: String,
+    },
     NoLocalRepoFound {
         operation: String,
     },
@@ -167,28 +233,62 @@ pub enum ErrorKind {
         dir: String,
     },
     BranchNameEmpty,
+    ChangeIdNotFound {
+        rev: String,
+    },
+    DifferentialRevisionNotFound {
+        rev: String,
+    },
+    WebsiteNotFound {
+        repo: String,
+    },
     InvalidUser {
         name: String,
     },
 }
 
+// Note: Messages may contain URLs with credentials or API tokens. `Display` of `ErrorKind` redacts
+// them from the message formatted by this struct.
+struct UnredactedMessage<'a>(&'a ErrorKind);
+
 impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = UnredactedMessage(self).to_string();
+        f.write_str(&credential::redact(&msg))
+    }
+}
+
+impl<'a> fmt::Display for UnredactedMessage<'a> {
     #[cfg(not(tarpaulin_include))]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use ErrorKind::*;
-        match self {
+        match self.0 {
             BrokenRepoFormat {input} => write!(f, "Invalid repository format '{}' or unknown remote. Note: Format must be one of 'repo', 'user/repo', 'host/user/repo', Git URL", input),
             CliParseFail(_) => write!(f, "Can't parse command line arguments"),
             OpenUrlFailure {url, msg} => write!(f, "{}: Cannot open URL {}", msg, url),
-            GitLabDiffNotSupported => write!(f, "GitLab does not support '..' for comparing diff between commits. Please use '...'"),
-            BitbucketDiffNotSupported => write!(f, "BitBucket does not support diff between commits (see https://bitbucket.org/site/master/issues/4779/ability-to-diff-between-any-two-commits)"),
-            AzureDevOpsNotSupported => write!(f, "Azure Devops does not currently support this operation"),
+            GitLabDiffNotSupported => write!(f, "This GitLab instance does not support '..' for comparing diff between commits. GitLab 13.9 or later is required. Please use '...'"),
+            BitbucketDiffNotSupported => write!(f, "Bitbucket does not support '..' for comparing diff between commits. Please use '...'"),
+            SourceHutDiffNotSupported => write!(f, "SourceHut does not support diff between commits"),
+            BitbucketServerNotSupported{feature} => write!(f, "Bitbucket Server does not support {}", feature),
+            AzureDevOpsNotSupported{feature} => write!(f, "Azure DevOps does not support {}", feature),
+            CodeCommitNotSupported{feature} => write!(f, "AWS CodeCommit does not support {}", feature),
+            GerritNotSupported{feature} => write!(f, "Gerrit does not support {}", feature),
+            CgitNotSupported{feature} => write!(f, "cgit does not support {}", feature),
+            GitWebNotSupported{feature} => write!(f, "GitWeb does not support {}", feature),
+            PhabricatorNotSupported{feature} => write!(f, "Phabricator does not support {}", feature),
+            PagureNotSupported{feature} => write!(f, "Pagure does not support {}", feature),
+            LaunchpadNotSupported{feature} => write!(f, "Launchpad does not support {}", feature),
+            GistNotSupported{feature} => write!(f, "GitHub Gist does not support {}", feature),
+            GitHubWikiNotSupported{feature} => write!(f, "GitHub Wiki does not support {}", feature),
             NoUserInPath{path} => write!(f, "Can't detect user name from path {}", path),
             NoRepoInPath{path} => write!(f, "Can't detect repository name from path {}", path),
-            UnknownHostingService {url} => write!(f, "Unknown hosting service for URL {}. If you want to use custom URL for GitHub Enterprise, please set $GIT_BRWS_GHE_URL_HOST", url),
+            UnknownHostingService {url} => write!(f, "Unknown hosting service for URL {}. If you want to use custom URL for GitHub Enterprise, GitLab, Gitea, Bitbucket Server, Gerrit, cgit, GitWeb, Phabricator or Pagure, please set $GIT_BRWS_GHE_URL_HOST, $GIT_BRWS_GITLAB_URL_HOST, $GIT_BRWS_GITEA_URL_HOST, $GIT_BRWS_BITBUCKET_SERVER_URL_HOST, $GIT_BRWS_GERRIT_URL_HOST, $GIT_BRWS_CGIT_URL_HOST, $GIT_BRWS_GITWEB_URL_HOST, $GIT_BRWS_PHABRICATOR_URL_HOST or $GIT_BRWS_PAGURE_URL_HOST", url),
             BrokenUrl {url, msg} => write!(f, "Broken URL '{}': {}", url, msg),
             PullReqNotSupported {service} => write!(f, "--pr or -p does not support the service {}", service),
             GitHubStatusFailure {status, msg} => write!(f, "GitHub API failure with response status {}: {}", status, msg),
+            ApiStatusFailure {service, status, msg} => write!(f, "{} API failure with response status {}: {}", service, status, msg),
+            ApiResponseParseError {service, msg} => write!(f, "Cannot parse {} API response: {}", service, msg),
+            InvalidToken {service, expected} => write!(f, "Invalid API token for {}. Token must be in format '{}'", service, expected),
             HttpClientError(_) => write!(f, "Network request failure"),
             IoError(_) => write!(f, "I/O error happened. Git command or current directory or file path may not exist"),
             GitCommandError{stderr, args} => {
@@ -206,6 +306,7 @@ impl fmt::Display for ErrorKind {
             GitObjectNotFound{kind, object, msg} => write!(f, "Git could not find {} '{}': {}", kind, object, msg),
             GitRootDirNotFound{cwd, stderr} => write!(f, "Cannot locate root directory at {:?}: {}", cwd, stderr),
             UnexpectedRemoteName(name) => write!(f, "Tracking name must be remote-url/branch-name: {}", name),
+            LocalRemoteLoop{paths} => write!(f, "Remotes of local repositories form a loop: {:?}", paths),
             WrongNumberOfArgs{expected, actual, kind} => write!(f, "Invalid number of arguments for {}. {} is expected but {} given", kind, expected, actual),
             DiffDotsNotFound => write!(f, "'..' or '...' must be contained for diff"),
             DiffHandIsEmpty{input} => write!(f, "Not a diff format since LHS and/or RHS is empty {}", input),
@@ -220,15 +321,22 @@ impl fmt::Display for ErrorKind {
             InvalidIssueNumberFormat => write!(f, "Issue number must start with '#' followed by numbers like #123"),
             LineSpecifiedForDir(path) => write!(f, "Directory cannot have line number: {:?}", path),
             EnvLoadError(_) => write!(f, "Cannot load environment variable"),
+            ConfigFileError{path, msg} => write!(f, "Cannot load config file {:?}: {}", path, msg),
+            CacheFileError{path, msg} => write!(f, "Cannot save cache file {:?}: {}", path, msg),
+            UrlTemplateNotFound{host, template} => write!(f, "URL template '{}' is not defined for host '{}' in config file", template, host),
+            BrokenUrlTemplate{template, msg} => write!(f, "Broken URL template '{}': {}", template, msg),
             NoLocalRepoFound{operation} => write!(f, ".git directory was not found. For {}, local repository must be known", operation),
             NoSearchResult{query} => write!(f, "No repository was hit for query '{}'", query),
             ArgsNotAllowed{flag, args} => write!(f, "{} option does not allow any command line argument. It opens page based on {{repo}}, but argument(s) {:?} retrives information from local directory.", flag, args),
-            GheTokenRequired => write!(f, "GitHub Enterprise requires API token. Please set $GIT_BRWS_GHE_TOKEN"),
+            GheTokenRequired => write!(f, "GitHub Enterprise requires API token. Please set $GIT_BRWS_GHE_TOKEN or 'token' of the host in config file"),
             BlameWithoutFilePath => write!(f, "File path is not given to blame"),
             CannotBlameDirectory{dir} => write!(f, "Cannot blame directory '{}'. Please specify file path", dir),
             UserBrowseCommandFailed{cmd, url, msg} => write!(f, "Command '{}' failed to open URL {}. Please check $GIT_BRWS_BROWSE_COMMAND. stderr: {}", cmd, url, msg),
             SpecifiedDirNotExist{dir} => write!(f, "Specified directory '{}' with -d option does not exist", dir),
             BranchNameEmpty => write!(f, "Branch name cannot be empty"),
+            WebsiteNotFound{repo} => write!(f, "Website is not configured for repository {}", repo),
+            ChangeIdNotFound{rev} => write!(f, "'Change-Id:' trailer was not found in commit message of '{}'. Please upload the commit to Gerrit with commit-msg hook", rev),
+            DifferentialRevisionNotFound{rev} => write!(f, "'Differential Revision:' trailer was not found in commit messages of '{}'. Please create a revision with `arc diff`", rev),
             InvalidUser{name} => write!(f, "Invalid user or organization name '{}'", name),
         }
     }
@@ -0,0 +1,76 @@
+use crate::api;
+use crate::error::{Error, ErrorKind, Result};
+use reqwest::header;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use serde_derive::Deserialize;
+
+const SERVICE: &str = "Gerrit";
+
+// Gerrit prepends this magic prefix to JSON responses to prevent XSSI:
+//   https://gerrit-review.googlesource.com/Documentation/rest-api.html#output
+const MAGIC_PREFIX: &str = ")]}'";
+
+#[derive(Debug, Deserialize)]
+struct Change {
+    #[serde(rename = "_number")]
+    number: u64,
+}
+
+pub fn parse_change_number(body: &str) -> Result<Option<u64>> {
+    let body = body.strip_prefix(MAGIC_PREFIX).unwrap_or(body);
+    let changes: Vec<Change> = api::parse_json(body, SERVICE)?;
+    Ok(changes.first().map(|c| c.number))
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    credential: Option<(&'a str, &'a str)>,
+    scheme: &'static str,
+    endpoint: &'a str,
+}
+
+impl<'a> Client<'a> {
+    // Note: Token is '{username}:{HTTP password}' since Gerrit uses HTTP basic authentication
+    pub fn build(
+        endpoint: &'a str,
+        token: &'a Option<impl AsRef<str>>,
+        https_proxy: &Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let credential = match token {
+            Some(token) => Some(token.as_ref().split_once(':').ok_or_else(|| {
+                Error::new(ErrorKind::InvalidToken {
+                    service: SERVICE,
+                    expected: "{username}:{HTTP password}",
+                })
+            })?),
+            None => None,
+        };
+        let (scheme, endpoint) = api::split_scheme(endpoint);
+
+        Ok(Self {
+            client: api::build_client(https_proxy)?,
+            credential,
+            scheme,
+            endpoint,
+        })
+    }
+
+    pub async fn send(&self, mut req: RequestBuilder) -> Result<String> {
+        req = req.header(header::ACCEPT, "application/json");
+        if let Some((user, password)) = self.credential {
+            req = req.basic_auth(user, Some(password));
+        }
+        api::send(req, SERVICE).await
+    }
+
+    pub async fn find_change_number(&self, change_id: &str, project: &str) -> Result<Option<u64>> {
+        // Note: Authenticated REST endpoints are prefixed with '/a/'
+        let prefix = if self.credential.is_some() { "/a" } else { "" };
+        let query = format!("change:{} project:{}", change_id, project);
+        let params = [("q", query.as_str()), ("n", "1")];
+        let url = format!("{}://{}{}/changes/", self.scheme, self.endpoint, prefix);
+        let req = self.client.get(url.as_str()).query(&params);
+        let body = self.send(req).await?;
+        parse_change_number(&body)
+    }
+}
@@ -5,6 +5,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str;
+use url::Url;
 
 fn object_not_found(kind: &'static str, inner: impl Display, object: impl ToString) -> Box<Error> {
     Error::new(ErrorKind::GitObjectNotFound {
@@ -14,18 +15,60 @@ fn object_not_found(kind: &'static str, inner: impl Display, object: impl ToStri
     })
 }
 
+// Rewrites the URL with the rule whose prefix is the longest among matched rules. Each rule is a
+// pair of (base, prefix). Returns `None` when no rule matches.
+pub fn rewrite_url(url: &str, rules: &[(String, String)]) -> Option<String> {
+    rules
+        .iter()
+        .filter(|(_, prefix)| !prefix.is_empty() && url.starts_with(prefix.as_str()))
+        .max_by_key(|(_, prefix)| prefix.len())
+        .map(|(base, prefix)| format!("{}{}", base, &url[prefix.len()..]))
+}
+
+// Returns the path of local repository when the remote URL points a local directory like
+// '/srv/mirrors/repo.git', '../repo.git' or 'file:///srv/mirrors/repo.git'. Relative paths are
+// relative to `base`.
+pub fn local_repo_path(url: &str, base: &Path) -> Option<PathBuf> {
+    if url.starts_with("file://") {
+        return Url::parse(url).ok()?.to_file_path().ok();
+    }
+    if url.contains("://") {
+        return None;
+    }
+    // Note: As well as git, it is scp-like syntax when ':' appears before the first '/'
+    //   https://git-scm.com/docs/git-clone#_git_urls
+    let path = Path::new(url);
+    let is_scp_like = match (url.find(':'), url.find('/')) {
+        (Some(colon), Some(slash)) => colon < slash,
+        (Some(_), None) => true,
+        _ => false,
+    };
+    if is_scp_like && !path.is_absolute() {
+        return None;
+    }
+    if url.starts_with('.') || path.is_absolute() || base.join(path).is_dir() {
+        Some(base.join(path))
+    } else {
+        None
+    }
+}
+
+const NULL_DEVICE: &str = if cfg!(windows) { "NUL" } else { "/dev/null" };
+
 pub struct Git<'a> {
     command: &'a str,
     cwd: &'a Path,
+    global_config: bool,
 }
 
 impl<'a> Git<'a> {
     pub fn command<S: AsRef<OsStr> + Debug>(&self, args: &[S]) -> Result<String> {
-        let out = Command::new(self.command)
-            .arg("-C")
-            .arg(self.cwd)
-            .args(args)
-            .output()?;
+        let mut cmd = Command::new(self.command);
+        if !self.global_config {
+            cmd.env("GIT_CONFIG_NOSYSTEM", "1")
+                .env("GIT_CONFIG_GLOBAL", NULL_DEVICE);
+        }
+        let out = cmd.arg("-C").arg(self.cwd).args(args).output()?;
         if out.status.success() {
             Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
         } else {
@@ -58,8 +101,69 @@ impl<'a> Git<'a> {
         // `git remote get-url {name}` is not available because it's added recently (at 2.6.1).
         // Note that git installed in Ubuntu 14.04 is 1.9.1.
         let name = name.as_ref();
-        self.command(&["config", "--get", &format!("remote.{}.url", name)])
-            .map_err(|e| object_not_found("remote", e, name))
+        let url = self
+            .command(&["config", "--get", &format!("remote.{}.url", name)])
+            .map_err(|e| object_not_found("remote", e, name))?;
+        Ok(self.rewrite_remote_url(url))
+    }
+
+    // Returns pairs of (base, prefix) configured as `url.{base}.{key} = {prefix}`.
+    // Note: `key` must be lower case since git outputs variable names in lower case.
+    pub fn url_rewrite_rules(&self, key: &str) -> Vec<(String, String)> {
+        let pattern = format!(r"^url\..*\.{}$", key);
+        // Note: `git config --get-regexp` exits with non-zero status when no entry is found
+        let stdout = match self.command(&["config", "-z", "--get-regexp", pattern.as_str()]) {
+            Ok(stdout) => stdout,
+            Err(_) => return vec![],
+        };
+        let suffix = format!(".{}", key);
+        // Each entry is formatted as '{name}\n{value}\0'
+        stdout
+            .split('\0')
+            .filter_map(|entry| {
+                let (name, prefix) = entry.trim_start().split_once('\n')?;
+                let base = name.strip_prefix("url.")?.strip_suffix(suffix.as_str())?;
+                Some((base.to_string(), prefix.to_string()))
+            })
+            .collect()
+    }
+
+    // Note: `url.{base}.insteadOf` rules are applied in the same way as git. `pushInsteadOf` rules
+    // are not applied since they only affect push URLs. After that, git-brws-specific
+    // `url.{base}.brwsInsteadOf` rules are applied. They are useful to map internal mirror hosts to
+    // their canonical web hosts without affecting git itself.
+    fn rewrite_remote_url(&self, url: String) -> String {
+        let url = rewrite_url(&url, &self.url_rewrite_rules("insteadof")).unwrap_or(url);
+        rewrite_url(&url, &self.url_rewrite_rules("brwsinsteadof")).unwrap_or(url)
+    }
+
+    // Note: Remote may point a local repository such as a mirror. In the case, 'origin' remotes of
+    // the local repositories are followed recursively until a URL of hosting service is found.
+    pub fn follow_local_remote(&self, url: String) -> Result<String> {
+        let mut url = url;
+        let mut base = self.root_dir().unwrap_or_else(|_| self.cwd.to_owned());
+        let mut visited = vec![];
+
+        while let Some(path) = local_repo_path(&url, &base) {
+            let path = match fs::canonicalize(&path) {
+                Ok(path) => path,
+                Err(_) => return Ok(url), // Not existing. Leave it to following steps
+            };
+            if visited.contains(&path) {
+                visited.push(path);
+                return Error::err(ErrorKind::LocalRemoteLoop { paths: visited });
+            }
+            let git = Git {
+                command: self.command,
+                cwd: &path,
+                global_config: self.global_config,
+            };
+            url = git.remote_url("origin")?;
+            base = path.clone();
+            visited.push(path);
+        }
+
+        Ok(url)
     }
 
     pub fn tracking_remote_url(
@@ -105,6 +209,38 @@ impl<'a> Git<'a> {
         }
     }
 
+    pub fn commit_message(&self, commit: impl AsRef<str>) -> Result<String> {
+        let commit = commit.as_ref();
+        self.command(&["log", "-1", "--format=%B", commit])
+            .map_err(|e| object_not_found("commit", e, commit))
+    }
+
+    // Note: Commit messages of commits which are not pushed to any remote yet, newest first
+    pub fn unpushed_commit_messages(&self, commit: impl AsRef<str>) -> Result<Vec<String>> {
+        let commit = commit.as_ref();
+        let stdout = self
+            .command(&["log", "--format=%B%x00", commit, "--not", "--remotes"])
+            .map_err(|e| object_not_found("commit", e, commit))?;
+        Ok(stdout
+            .split('\0')
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    // Note: refs/remotes/{remote}/HEAD is set by `git clone` or `git remote set-head`
+    pub fn remote_default_branch(&self, remote: impl AsRef<str>) -> Result<String> {
+        let remote = remote.as_ref();
+        let head = format!("refs/remotes/{}/HEAD", remote);
+        let out = self.command(&["symbolic-ref", "--short", head.as_str()])?;
+        // out is formatted as '{remote-name}/{branch-name}'
+        match out.strip_prefix(remote).and_then(|b| b.strip_prefix('/')) {
+            Some(branch) => Ok(branch.to_string()),
+            None => Error::err(ErrorKind::UnexpectedRemoteName(out.clone())),
+        }
+    }
+
     pub fn current_branch(&self) -> Result<String> {
         self.command(&["rev-parse", "--abbrev-ref", "--symbolic", "HEAD"])
     }
@@ -145,6 +281,19 @@ impl<'a> Git<'a> {
 
 impl<'a> Git<'a> {
     pub fn new(cwd: &'a Path, command: &'a str) -> Git<'a> {
-        Git { command, cwd }
+        Git {
+            command,
+            cwd,
+            global_config: true,
+        }
+    }
+
+    // Note: System-wide and global git config (e.g. ~/.gitconfig) are not read. It requires git 2.32
+    // or later for ignoring global config.
+    pub fn without_global_config(self) -> Git<'a> {
+        Git {
+            global_config: false,
+            ..self
+        }
     }
 }
@@ -0,0 +1,117 @@
+use crate::api;
+use crate::error::Result;
+use reqwest::header;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use serde_derive::Deserialize;
+
+const SERVICE: &str = "Gitea";
+
+#[derive(Debug, Deserialize)]
+pub struct Owner {
+    pub login: String,
+}
+#[derive(Debug, Deserialize)]
+pub struct ParentRepo {
+    pub name: String,
+    pub owner: Owner,
+}
+#[derive(Debug, Deserialize)]
+pub struct Repo {
+    pub parent: Option<ParentRepo>,
+    #[serde(default)]
+    pub website: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HeadRepo {
+    owner: Owner,
+}
+#[derive(Debug, Deserialize)]
+struct Head {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    repo: Option<HeadRepo>,
+}
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    html_url: String,
+    head: Head,
+}
+
+// Note: Gitea does not provide an API to search pull requests by head branch. Open pull requests
+// are filtered on client side instead. When `pr_author` is set, only pull requests sent from the
+// author's fork are matched.
+pub fn parse_pr_url(body: &str, branch: &str, pr_author: Option<&str>) -> Result<Option<String>> {
+    let pulls: Vec<PullRequest> = api::parse_json(body, SERVICE)?;
+    Ok(pulls
+        .into_iter()
+        .find(|pr| {
+            pr.head.ref_name == branch
+                && match (pr_author, &pr.head.repo) {
+                    (Some(author), Some(repo)) => repo.owner.login == author,
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                }
+        })
+        .map(|pr| pr.html_url))
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    token: Option<&'a str>,
+    scheme: &'static str,
+    endpoint: &'a str,
+}
+
+impl<'a> Client<'a> {
+    pub fn build(
+        endpoint: &'a str,
+        token: &'a Option<impl AsRef<str>>,
+        https_proxy: &Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let (scheme, endpoint) = api::split_scheme(endpoint);
+        Ok(Self {
+            client: api::build_client(https_proxy)?,
+            token: token.as_ref().map(AsRef::as_ref),
+            scheme,
+            endpoint,
+        })
+    }
+
+    pub async fn send(&self, mut req: RequestBuilder) -> Result<String> {
+        req = req.header(header::ACCEPT, "application/json");
+        if let Some(token) = &self.token {
+            req = req.header(header::AUTHORIZATION, format!("token {}", token));
+        }
+        api::send(req, SERVICE).await
+    }
+
+    pub async fn find_pr_url(
+        &self,
+        branch: &str,
+        owner: &str,
+        repo: &str,
+        pr_author: Option<&str>,
+    ) -> Result<Option<String>> {
+        let params = [("state", "open"), ("sort", "recentupdate"), ("limit", "50")];
+        let url = format!(
+            "{}://{}/repos/{}/{}/pulls",
+            self.scheme, self.endpoint, owner, repo
+        );
+        let req = self.client.get(url.as_str()).query(&params);
+        let body = self.send(req).await?;
+        parse_pr_url(&body, branch, pr_author)
+    }
+
+    pub async fn repo(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> Result<Repo> {
+        let owner = owner.as_ref();
+        let repo = repo.as_ref();
+        let url = format!(
+            "{}://{}/repos/{}/{}",
+            self.scheme, self.endpoint, owner, repo
+        );
+        let req = self.client.get(url.as_str());
+        let body = self.send(req).await?;
+        api::parse_json(&body, SERVICE)
+    }
+}
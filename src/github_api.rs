@@ -41,9 +41,19 @@ struct RepoForHomepage {
     homepage: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GistOwner {
+    login: String,
+}
+#[derive(Debug, Deserialize)]
+struct Gist {
+    owner: Option<GistOwner>,
+}
+
 pub struct Client<'a> {
     client: ReqwestClient,
     token: Option<&'a str>,
+    scheme: &'static str,
     endpoint: &'a str,
 }
 
@@ -63,9 +73,16 @@ impl<'a> Client<'a> {
             }
         }
 
+        // Note: Endpoint is accessed via HTTPS unless it explicitly starts with 'http://'
+        let (scheme, endpoint) = match endpoint.strip_prefix("http://") {
+            Some(endpoint) => ("http", endpoint),
+            None => ("https", endpoint),
+        };
+
         Ok(Self {
             client: b.build()?,
             token: token.as_ref().map(AsRef::as_ref),
+            scheme,
             endpoint,
         })
     }
@@ -105,7 +122,7 @@ impl<'a> Client<'a> {
             format!("type:pr head:{} repo:{}/{}", branch, owner, repo)
         };
         let params = [("q", query.as_str()), ("sort", "updated")];
-        let url = format!("https://{}/search/issues", self.endpoint);
+        let url = format!("{}://{}/search/issues", self.scheme, self.endpoint);
         let req = self.client.get(url.as_str()).query(&params);
         let res = self.send(req).await?;
         let mut issues: Issues = res.json().await?;
@@ -121,7 +138,10 @@ impl<'a> Client<'a> {
     pub async fn repo(&self, author: impl AsRef<str>, repo: impl AsRef<str>) -> Result<Repo> {
         let author = author.as_ref();
         let repo = repo.as_ref();
-        let url = format!("https://{}/repos/{}/{}", self.endpoint, author, repo);
+        let url = format!(
+            "{}://{}/repos/{}/{}",
+            self.scheme, self.endpoint, author, repo
+        );
         let req = self.client.get(url.as_str());
         let res = self.send(req).await?;
         let repo: Repo = res.json().await?;
@@ -133,7 +153,7 @@ impl<'a> Client<'a> {
         // it's matching to substrings.
         let query = format!("{} in:name", name.as_ref());
         let params = [("q", query.as_str()), ("per_page", "1")];
-        let url = format!("https://{}/search/repositories", self.endpoint);
+        let url = format!("{}://{}/search/repositories", self.scheme, self.endpoint);
         let req = self.client.get(&url).query(&params);
         let res = self.send(req).await?;
         let mut results: SearchResults = res.json().await?;
@@ -152,10 +172,22 @@ impl<'a> Client<'a> {
     ) -> Result<Option<String>> {
         let owner = owner.as_ref();
         let repo = repo.as_ref();
-        let url = format!("https://{}/repos/{}/{}", self.endpoint, owner, repo);
+        let url = format!(
+            "{}://{}/repos/{}/{}",
+            self.scheme, self.endpoint, owner, repo
+        );
         let req = self.client.get(url.as_str());
         let res = self.send(req).await?;
         let repo: RepoForHomepage = res.json().await?;
         Ok(repo.homepage)
     }
+
+    // Note: Anonymous gist has no owner
+    pub async fn gist_owner(&self, id: impl AsRef<str>) -> Result<Option<String>> {
+        let url = format!("{}://{}/gists/{}", self.scheme, self.endpoint, id.as_ref());
+        let req = self.client.get(url.as_str());
+        let res = self.send(req).await?;
+        let gist: Gist = res.json().await?;
+        Ok(gist.owner.map(|o| o.login))
+    }
 }
@@ -0,0 +1,123 @@
+use crate::api;
+use crate::error::Result;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use serde_derive::Deserialize;
+use url::form_urlencoded;
+
+const SERVICE: &str = "GitLab";
+
+#[derive(Debug, Deserialize)]
+pub struct ParentProject {
+    pub id: u64,
+    pub path_with_namespace: String,
+}
+#[derive(Debug, Deserialize)]
+pub struct Project {
+    pub id: u64,
+    pub forked_from_project: Option<ParentProject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Version {
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    web_url: String,
+    source_project_id: u64,
+}
+
+// Note: When `source_project_id` is set, only merge requests sent from the project are matched.
+// It is used for finding merge requests sent from a fork.
+pub fn parse_mr_url(body: &str, source_project_id: Option<u64>) -> Result<Option<String>> {
+    let mrs: Vec<MergeRequest> = api::parse_json(body, SERVICE)?;
+    Ok(mrs
+        .into_iter()
+        .find(|mr| match source_project_id {
+            Some(id) => mr.source_project_id == id,
+            None => true,
+        })
+        .map(|mr| mr.web_url))
+}
+
+pub fn parse_version(body: &str) -> Result<String> {
+    let v: Version = api::parse_json(body, SERVICE)?;
+    Ok(v.version)
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    token: Option<&'a str>,
+    scheme: &'static str,
+    endpoint: &'a str,
+}
+
+impl<'a> Client<'a> {
+    pub fn build(
+        endpoint: &'a str,
+        token: &'a Option<impl AsRef<str>>,
+        https_proxy: &Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let (scheme, endpoint) = api::split_scheme(endpoint);
+        Ok(Self {
+            client: api::build_client(https_proxy)?,
+            token: token.as_ref().map(AsRef::as_ref),
+            scheme,
+            endpoint,
+        })
+    }
+
+    pub async fn send(&self, mut req: RequestBuilder) -> Result<String> {
+        // Personal access token is sent via 'PRIVATE-TOKEN' header:
+        //   https://docs.gitlab.com/ee/api/rest/#personalprojectgroup-access-tokens
+        if let Some(token) = &self.token {
+            req = req.header("PRIVATE-TOKEN", *token);
+        }
+        api::send(req, SERVICE).await
+    }
+
+    pub async fn find_mr_url(
+        &self,
+        branch: &str,
+        project: &str,
+        source_project_id: Option<u64>,
+    ) -> Result<Option<String>> {
+        let params = [("state", "opened"), ("source_branch", branch)];
+        let url = format!(
+            "{}://{}/projects/{}/merge_requests",
+            self.scheme,
+            self.endpoint,
+            encode_path(project),
+        );
+        let req = self.client.get(url.as_str()).query(&params);
+        let body = self.send(req).await?;
+        parse_mr_url(&body, source_project_id)
+    }
+
+    // Note: `project` is a path with namespace like 'group/subgroup/repo'
+    pub async fn project(&self, project: &str) -> Result<Project> {
+        let url = format!(
+            "{}://{}/projects/{}",
+            self.scheme,
+            self.endpoint,
+            encode_path(project)
+        );
+        let req = self.client.get(url.as_str());
+        let body = self.send(req).await?;
+        api::parse_json(&body, SERVICE)
+    }
+
+    // Note: Returns a version string like '16.5.1-ee'. This API requires authentication.
+    pub async fn version(&self) -> Result<String> {
+        let url = format!("{}://{}/version", self.scheme, self.endpoint);
+        let req = self.client.get(url.as_str());
+        let body = self.send(req).await?;
+        parse_version(&body)
+    }
+}
+
+// Note: Project path is used as project ID with URL-encoding ('/' is encoded as '%2F')
+fn encode_path(path: &str) -> String {
+    form_urlencoded::byte_serialize(path.as_bytes()).collect()
+}
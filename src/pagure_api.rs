@@ -0,0 +1,72 @@
+use crate::api;
+use crate::error::Result;
+use reqwest::header;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use serde_derive::Deserialize;
+
+const SERVICE: &str = "Pagure";
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    id: u64,
+    branch_from: String,
+}
+#[derive(Debug, Deserialize)]
+struct PullRequests {
+    requests: Vec<PullRequest>,
+}
+
+// Note: Pagure does not provide a filter by head branch so open pull requests are filtered on
+// client side.
+//   https://pagure.io/api/0/#pull_requests-tab
+pub fn parse_pr_id(body: &str, branch: &str) -> Result<Option<u64>> {
+    let pulls: PullRequests = api::parse_json(body, SERVICE)?;
+    Ok(pulls
+        .requests
+        .into_iter()
+        .find(|pr| pr.branch_from == branch)
+        .map(|pr| pr.id))
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    token: Option<&'a str>,
+    scheme: &'static str,
+    endpoint: &'a str,
+}
+
+impl<'a> Client<'a> {
+    pub fn build(
+        endpoint: &'a str,
+        token: &'a Option<impl AsRef<str>>,
+        https_proxy: &Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let (scheme, endpoint) = api::split_scheme(endpoint);
+        Ok(Self {
+            client: api::build_client(https_proxy)?,
+            token: token.as_ref().map(AsRef::as_ref),
+            scheme,
+            endpoint,
+        })
+    }
+
+    pub async fn send(&self, mut req: RequestBuilder) -> Result<String> {
+        req = req.header(header::ACCEPT, "application/json");
+        if let Some(token) = &self.token {
+            req = req.header(header::AUTHORIZATION, format!("token {}", token));
+        }
+        api::send(req, SERVICE).await
+    }
+
+    // Note: `project` is a path of the project such as 'rpms/bash' or 'fork/user/repo'
+    pub async fn find_pr_id(&self, branch: &str, project: &str) -> Result<Option<u64>> {
+        let params = [("status", "Open"), ("per_page", "100")];
+        let url = format!(
+            "{}://{}/api/0/{}/pull-requests",
+            self.scheme, self.endpoint, project
+        );
+        let req = self.client.get(url.as_str()).query(&params);
+        let body = self.send(req).await?;
+        parse_pr_id(&body, branch)
+    }
+}
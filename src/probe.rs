@@ -0,0 +1,222 @@
+use crate::async_runtime;
+use crate::config::{EnvConfig, HostKind};
+use crate::error::{Error, ErrorKind, Result};
+use reqwest::{Client as ReqwestClient, Proxy, StatusCode};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Hosts where no service was detected are probed again after this period
+const UNKNOWN_HOST_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Service {
+    #[serde(rename = "github")]
+    GitHub,
+    #[serde(rename = "gitlab")]
+    GitLab,
+    #[serde(rename = "gitea")]
+    Gitea,
+    #[serde(rename = "bitbucket_server")]
+    BitbucketServer,
+}
+
+impl Service {
+    pub fn host_kind(self) -> Option<HostKind> {
+        match self {
+            Service::GitHub => Some(HostKind::GitHub),
+            Service::GitLab => Some(HostKind::GitLab),
+            Service::Gitea => Some(HostKind::Gitea),
+            Service::BitbucketServer => None,
+        }
+    }
+}
+
+// Services detected by probing are cached per host on disk since probing needs several requests.
+// Hosts where no service was detected are also cached with the time of probing so that they are not
+// probed on every run.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Cache {
+    pub hosts: BTreeMap<String, Service>,
+    // Note: Value is seconds since UNIX epoch when the host was probed
+    #[serde(default)]
+    pub unknown_hosts: BTreeMap<String, u64>,
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Cache {
+    pub fn is_recently_unknown(&self, host: &str, now: u64) -> bool {
+        match self.unknown_hosts.get(host) {
+            Some(probed) => now.saturating_sub(*probed) < UNKNOWN_HOST_TTL.as_secs(),
+            None => false,
+        }
+    }
+
+    fn path(env: &EnvConfig) -> Option<PathBuf> {
+        match &env.cache_file {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::cache_dir().map(|dir| dir.join("git-brws").join("hosts.json")),
+        }
+    }
+
+    // Note: Missing or broken cache file is treated as empty cache
+    pub fn load(env: &EnvConfig) -> Cache {
+        Self::path(env)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, env: &EnvConfig) -> Result<()> {
+        let path = match Self::path(env) {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let cache_error = |msg: String| {
+            Error::new(ErrorKind::CacheFileError {
+                path: path.clone(),
+                msg,
+            })
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| cache_error(format!("{}", e)))?;
+        }
+        let content =
+            serde_json::to_string_pretty(self).map_err(|e| cache_error(format!("{}", e)))?;
+        fs::write(&path, content).map_err(|e| cache_error(format!("{}", e)))
+    }
+}
+
+async fn probe(
+    client: &ReqwestClient,
+    url: String,
+    matches: impl Fn(StatusCode, &Value) -> bool,
+) -> bool {
+    let res = match client.get(url.as_str()).send().await {
+        Ok(res) => res,
+        Err(_) => return false,
+    };
+    let status = res.status();
+    match res.json::<Value>().await {
+        Ok(json) => matches(status, &json),
+        Err(_) => false,
+    }
+}
+
+// Note: `origin` is like 'https://example.com' or 'http://example.com:8080'. All endpoints are
+// probed at once and the first matched service in the order of GitHub Enterprise, GitLab, Gitea
+// and Bitbucket Server is returned.
+pub async fn probe_service(
+    origin: &str,
+    https_proxy: &Option<impl AsRef<str>>,
+) -> Result<Option<Service>> {
+    let mut b = ReqwestClient::builder()
+        .user_agent("git-brws")
+        .timeout(Duration::from_secs(5));
+
+    if let Some(p) = https_proxy {
+        let p = p.as_ref();
+        if !p.is_empty() {
+            b = b.proxy(Proxy::https(p)?);
+        }
+    }
+
+    let client = b.build()?;
+
+    let (github, gitlab, gitea, bitbucket_server) = futures::join!(
+        // https://docs.github.com/en/enterprise-server/rest/meta/meta
+        probe(
+            &client,
+            format!("{}/api/v3/meta", origin),
+            |status, json| {
+                status == StatusCode::OK
+                    && (json.get("installed_version").is_some()
+                        || json.get("verifiable_password_authentication").is_some())
+            }
+        ),
+        // Note: Version API of GitLab requires authentication
+        //   https://docs.gitlab.com/ee/api/version.html
+        probe(
+            &client,
+            format!("{}/api/v4/version", origin),
+            |status, json| {
+                match status {
+                    StatusCode::OK => json.get("revision").is_some(),
+                    StatusCode::UNAUTHORIZED => {
+                        json.get("message").and_then(Value::as_str) == Some("401 Unauthorized")
+                    }
+                    _ => false,
+                }
+            }
+        ),
+        // https://docs.gitea.com/api/1.20/#tag/miscellaneous/operation/getVersion
+        probe(
+            &client,
+            format!("{}/api/v1/version", origin),
+            |status, json| {
+                status == StatusCode::OK && json.get("version").and_then(Value::as_str).is_some()
+            }
+        ),
+        // https://docs.atlassian.com/bitbucket-server/rest/5.16.0/bitbucket-rest.html#idm8297447584
+        probe(
+            &client,
+            format!("{}/rest/api/1.0/application-properties", origin),
+            |status, json| {
+                status == StatusCode::OK
+                    && json.get("displayName").and_then(Value::as_str) == Some("Bitbucket")
+            },
+        ),
+    );
+
+    let detected = [
+        (github, Service::GitHub),
+        (gitlab, Service::GitLab),
+        (gitea, Service::Gitea),
+        (bitbucket_server, Service::BitbucketServer),
+    ]
+    .iter()
+    .find(|(matched, _)| *matched)
+    .map(|(_, service)| *service);
+
+    Ok(detected)
+}
+
+// Note: Detection can be disabled with $GIT_BRWS_PROBE_UNKNOWN_HOSTS=false. Cached services are not
+// used either in the case.
+pub fn detect_service(host: &str, origin: &str, env: &EnvConfig) -> Result<Option<Service>> {
+    if !env.probe_unknown_hosts {
+        return Ok(None);
+    }
+
+    let mut cache = Cache::load(env);
+    if let Some(service) = cache.hosts.get(host) {
+        return Ok(Some(*service));
+    }
+    let now = unix_time();
+    if cache.is_recently_unknown(host, now) {
+        return Ok(None);
+    }
+
+    let detected = async_runtime::blocking(probe_service(origin, &env.https_proxy))?;
+    match detected {
+        Some(service) => {
+            cache.hosts.insert(host.to_string(), service);
+            cache.unknown_hosts.remove(host);
+        }
+        None => {
+            cache.unknown_hosts.insert(host.to_string(), now);
+        }
+    }
+    // Note: Failing to write the cache is not fatal. The host will be probed again next time
+    let _ = cache.save(env);
+    Ok(detected)
+}
@@ -1,6 +1,13 @@
+use crate::azure_devops_api;
+use crate::bitbucket_api;
+use crate::bitbucket_server_api;
 use crate::config::{Config, EnvConfig};
 use crate::error::{Error, ErrorKind, Result};
+use crate::gerrit_api;
+use crate::gitea_api;
 use crate::github_api;
+use crate::gitlab_api;
+use crate::pagure_api;
 use std::borrow::Cow;
 
 #[derive(PartialEq, Debug, Eq)]
@@ -24,19 +31,15 @@ pub enum Page<'a, 'b> {
 async fn find_github_pr_url_for_branch<'a, 'b>(
     branch: impl AsRef<str>,
     endpoint: &str,
+    token: &Option<String>,
     author: &'a str,
     repo: &'b str,
     env: &EnvConfig,
 ) -> Result<Page<'a, 'b>> {
     let branch = branch.as_ref();
-    let token = if endpoint == "api.github.com" {
-        &env.github_token
-    } else {
-        if env.ghe_token.is_none() {
-            return Error::err(ErrorKind::GheTokenRequired);
-        }
-        &env.ghe_token
-    };
+    if endpoint != "api.github.com" && token.is_none() {
+        return Error::err(ErrorKind::GheTokenRequired);
+    }
     let client = github_api::Client::build(endpoint, token, &env.https_proxy)?;
 
     let (pr_url, fetched_repo) = futures::join!(
@@ -85,16 +88,18 @@ async fn find_github_pr_url_for_branch<'a, 'b>(
 
 pub async fn find_page<'a, 'b>(
     endpoint: &str,
+    token: &Option<String>,
     author: &'a str,
     repo: &'b str,
     cfg: &Config,
 ) -> Result<Page<'a, 'b>> {
     if let Some(b) = &cfg.branch {
-        find_github_pr_url_for_branch(b, endpoint, author, repo, &cfg.env).await
+        find_github_pr_url_for_branch(b, endpoint, token, author, repo, &cfg.env).await
     } else {
         find_github_pr_url_for_branch(
             cfg.git().current_branch()?,
             endpoint,
+            token,
             author,
             repo,
             &cfg.env,
@@ -102,3 +107,384 @@ pub async fn find_page<'a, 'b>(
         .await
     }
 }
+
+// Note: New merge request page is opened at source project. When the source project is a fork,
+// target project must be specified with its ID.
+#[derive(PartialEq, Debug, Eq)]
+pub enum GitLabPage {
+    Existing {
+        url: String,
+    },
+    New {
+        branch: String,
+        target_project_id: Option<u64>,
+    },
+}
+
+async fn find_gitlab_mr_url_for_branch(
+    branch: String,
+    endpoint: &str,
+    token: &Option<String>,
+    project: &str,
+    env: &EnvConfig,
+) -> Result<GitLabPage> {
+    let client = gitlab_api::Client::build(endpoint, token, &env.https_proxy)?;
+
+    let (mr_url, fetched_project) = futures::join!(
+        client.find_mr_url(&branch, project, None),
+        client.project(project),
+    );
+
+    if let Some(url) = mr_url? {
+        return Ok(GitLabPage::Existing { url });
+    }
+
+    let fetched_project = fetched_project?;
+    if let Some(parent) = fetched_project.forked_from_project {
+        // Note: Other person may create another merge request with the same branch name
+        if let Some(url) = client
+            .find_mr_url(
+                &branch,
+                &parent.path_with_namespace,
+                Some(fetched_project.id),
+            )
+            .await?
+        {
+            Ok(GitLabPage::Existing { url })
+        } else {
+            Ok(GitLabPage::New {
+                branch,
+                target_project_id: Some(parent.id),
+            })
+        }
+    } else {
+        Ok(GitLabPage::New {
+            branch,
+            target_project_id: None,
+        })
+    }
+}
+
+// Note: `project` is a path with namespace like 'group/subgroup/repo'
+pub async fn find_gitlab_page(
+    endpoint: &str,
+    token: &Option<String>,
+    project: &str,
+    cfg: &Config,
+) -> Result<GitLabPage> {
+    let branch = match &cfg.branch {
+        Some(b) => b.clone(),
+        None => cfg.git().current_branch()?,
+    };
+    find_gitlab_mr_url_for_branch(branch, endpoint, token, project, &cfg.env).await
+}
+
+#[derive(PartialEq, Debug, Eq)]
+pub enum AzureDevOpsPage {
+    Existing {
+        id: u64,
+    },
+    New {
+        branch: String,
+        target: Option<String>,
+    },
+}
+
+// Note: Default branch is read from refs/remotes/{remote}/HEAD at first since it does not need
+// a network access. When it is not set, it is fetched via API. When the API is not available (e.g.
+// private project without token), the page to create a pull request is opened instead of an error.
+pub async fn find_azure_devops_page(
+    org: &str,
+    project: &str,
+    repo: &str,
+    cfg: &Config,
+) -> Result<AzureDevOpsPage> {
+    let branch = match &cfg.branch {
+        Some(b) => b.clone(),
+        None => cfg.git().current_branch()?,
+    };
+    let endpoint = format!("dev.azure.com/{}/{}", org, project);
+    let client = azure_devops_api::Client::build(
+        &endpoint,
+        &cfg.env.azure_devops_token,
+        &cfg.env.https_proxy,
+    )?;
+
+    let found = client.find_pr_id(&branch, repo).await;
+    if let Ok(Some(id)) = found {
+        return Ok(AzureDevOpsPage::Existing { id });
+    }
+
+    let remote = cfg.remote.as_deref().unwrap_or("origin");
+    let target = match cfg.git().remote_default_branch(remote) {
+        Ok(b) => Some(b),
+        // Note: Default branch is selected on the page when target is omitted
+        Err(_) if found.is_ok() => client.default_branch(repo).await.unwrap_or(None),
+        Err(_) => None,
+    };
+    Ok(AzureDevOpsPage::New { branch, target })
+}
+
+async fn find_gitea_pr_url_for_branch<'a, 'b>(
+    branch: impl AsRef<str>,
+    endpoint: &str,
+    token: &Option<String>,
+    author: &'a str,
+    repo: &'b str,
+    env: &EnvConfig,
+) -> Result<Page<'a, 'b>> {
+    let branch = branch.as_ref();
+    let client = gitea_api::Client::build(endpoint, token, &env.https_proxy)?;
+
+    let (pr_url, fetched_repo) = futures::join!(
+        client.find_pr_url(branch, author, repo, None),
+        client.repo(author, repo),
+    );
+
+    if let Some(url) = pr_url? {
+        return Ok(Page::Existing { url });
+    }
+
+    let fetched_repo = fetched_repo?;
+    if let Some(parent) = fetched_repo.parent {
+        let owner = parent.owner.login;
+        let repo = parent.name;
+
+        if let Some(url) = client
+            .find_pr_url(branch, owner.as_str(), repo.as_str(), Some(author))
+            .await?
+        {
+            Ok(Page::Existing { url })
+        } else {
+            Ok(Page::NewAtParent {
+                author: Cow::Owned(owner),
+                repo: Cow::Owned(repo),
+                fork_author: author,
+                branch: branch.to_string(),
+            })
+        }
+    } else {
+        Ok(Page::New {
+            author: Cow::Borrowed(author),
+            repo: Cow::Borrowed(repo),
+            branch: branch.to_string(),
+        })
+    }
+}
+
+pub async fn find_gitea_page<'a, 'b>(
+    endpoint: &str,
+    token: &Option<String>,
+    author: &'a str,
+    repo: &'b str,
+    cfg: &Config,
+) -> Result<Page<'a, 'b>> {
+    if let Some(b) = &cfg.branch {
+        find_gitea_pr_url_for_branch(b, endpoint, token, author, repo, &cfg.env).await
+    } else {
+        find_gitea_pr_url_for_branch(
+            cfg.git().current_branch()?,
+            endpoint,
+            token,
+            author,
+            repo,
+            &cfg.env,
+        )
+        .await
+    }
+}
+
+async fn find_bitbucket_pr_url_for_branch<'a, 'b>(
+    branch: impl AsRef<str>,
+    author: &'a str,
+    repo: &'b str,
+    env: &EnvConfig,
+) -> Result<Page<'a, 'b>> {
+    let branch = branch.as_ref();
+    let client = bitbucket_api::Client::build(
+        "api.bitbucket.org/2.0",
+        &env.bitbucket_token,
+        &env.https_proxy,
+    )?;
+
+    let (pr_url, fetched_repo) = futures::join!(
+        client.find_pr_url(branch, author, repo, None),
+        client.repo(author, repo),
+    );
+
+    if let Some(url) = pr_url? {
+        return Ok(Page::Existing { url });
+    }
+
+    if let Some(parent) = fetched_repo?.parent {
+        if let Some((owner, parent_repo)) = parent.full_name.split_once('/') {
+            let source = format!("{}/{}", author, repo);
+            if let Some(url) = client
+                .find_pr_url(branch, owner, parent_repo, Some(&source))
+                .await?
+            {
+                return Ok(Page::Existing { url });
+            }
+        }
+    }
+
+    // Note: Bitbucket selects the parent repository as destination by default when a pull request
+    // is created at a fork
+    Ok(Page::New {
+        author: Cow::Borrowed(author),
+        repo: Cow::Borrowed(repo),
+        branch: branch.to_string(),
+    })
+}
+
+pub async fn find_bitbucket_page<'a, 'b>(
+    author: &'a str,
+    repo: &'b str,
+    cfg: &Config,
+) -> Result<Page<'a, 'b>> {
+    if let Some(b) = &cfg.branch {
+        find_bitbucket_pr_url_for_branch(b, author, repo, &cfg.env).await
+    } else {
+        find_bitbucket_pr_url_for_branch(cfg.git().current_branch()?, author, repo, &cfg.env).await
+    }
+}
+
+// Note: Forks on Bitbucket Server are personal repositories. Only pull requests from the repository
+// itself are searched.
+async fn find_bitbucket_server_pr_url_for_branch<'a, 'b>(
+    branch: impl AsRef<str>,
+    endpoint: &str,
+    project: &'a str,
+    repo: &'b str,
+    env: &EnvConfig,
+) -> Result<Page<'a, 'b>> {
+    let branch = branch.as_ref();
+    let client = bitbucket_server_api::Client::build(
+        endpoint,
+        &env.bitbucket_server_token,
+        &env.https_proxy,
+    )?;
+
+    if let Some(url) = client.find_pr_url(branch, project, repo).await? {
+        Ok(Page::Existing { url })
+    } else {
+        Ok(Page::New {
+            author: Cow::Borrowed(project),
+            repo: Cow::Borrowed(repo),
+            branch: branch.to_string(),
+        })
+    }
+}
+
+pub async fn find_bitbucket_server_page<'a, 'b>(
+    endpoint: &str,
+    project: &'a str,
+    repo: &'b str,
+    cfg: &Config,
+) -> Result<Page<'a, 'b>> {
+    if let Some(b) = &cfg.branch {
+        find_bitbucket_server_pr_url_for_branch(b, endpoint, project, repo, &cfg.env).await
+    } else {
+        find_bitbucket_server_pr_url_for_branch(
+            cfg.git().current_branch()?,
+            endpoint,
+            project,
+            repo,
+            &cfg.env,
+        )
+        .await
+    }
+}
+
+// Note: `project` is a path of project such as 'rpms/bash'. It is split into namespace and name as
+// author and repo of the page. `origin` is '{scheme}://{host}[:{port}]' of the instance.
+pub async fn find_pagure_page<'a>(
+    origin: &str,
+    project: &'a str,
+    cfg: &Config,
+) -> Result<Page<'a, 'a>> {
+    let branch = match &cfg.branch {
+        Some(b) => b.clone(),
+        None => cfg.git().current_branch()?,
+    };
+    let client = pagure_api::Client::build(origin, &cfg.env.pagure_token, &cfg.env.https_proxy)?;
+
+    if let Some(id) = client.find_pr_id(&branch, project).await? {
+        return Ok(Page::Existing {
+            url: format!("{}/{}/pull-request/{}", origin, project, id),
+        });
+    }
+
+    let (author, repo) = project.rsplit_once('/').unwrap_or(("", project));
+    Ok(Page::New {
+        author: Cow::Borrowed(author),
+        repo: Cow::Borrowed(repo),
+        branch,
+    })
+}
+
+// Note: Trailers are 'Key: value' lines in the last paragraph of a commit message. When the same
+// key appears multiple times, the last one is used.
+pub fn find_trailer<'a>(message: &'a str, key: &str) -> Option<&'a str> {
+    let last_paragraph = message.trim_end().rsplit("\n\n").next()?;
+    last_paragraph.lines().rev().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        let v = v.trim();
+        if k.trim().eq_ignore_ascii_case(key) && !v.is_empty() {
+            Some(v)
+        } else {
+            None
+        }
+    })
+}
+
+// Gerrit identifies a change by 'Change-Id:' trailer added by commit-msg hook:
+//   https://gerrit-review.googlesource.com/Documentation/user-changeid.html
+// Note: `origin` is '{scheme}://{host}[:{port}]' of the Gerrit instance
+pub async fn find_gerrit_change_url(origin: &str, project: &str, cfg: &Config) -> Result<String> {
+    let rev = cfg.branch.as_deref().unwrap_or("HEAD");
+    let message = cfg.git().commit_message(rev)?;
+    let change_id = find_trailer(&message, "Change-Id").ok_or_else(|| {
+        Error::new(ErrorKind::ChangeIdNotFound {
+            rev: rev.to_string(),
+        })
+    })?;
+
+    if cfg.env.gerrit_token.is_some() {
+        let client =
+            gerrit_api::Client::build(origin, &cfg.env.gerrit_token, &cfg.env.https_proxy)?;
+        if let Some(number) = client.find_change_number(change_id, project).await? {
+            return Ok(format!("{}/c/{}/+/{}", origin, project, number));
+        }
+    }
+
+    // Note: Gerrit redirects to the change page when the query matches exactly one change
+    Ok(format!("{}/q/{}", origin, change_id))
+}
+
+// `arc diff` adds 'Differential Revision:' trailer with URL of the revision:
+//   https://secure.phabricator.com/book/phabricator/article/arcanist_diff/
+// Note: When all commits of the branch were already pushed, only the head commit is checked.
+pub fn find_differential_revision_url(origin: &str, cfg: &Config) -> Result<String> {
+    let rev = cfg.branch.as_deref().unwrap_or("HEAD");
+    let git = cfg.git();
+    let mut messages = git.unpushed_commit_messages(rev)?;
+    if messages.is_empty() {
+        messages.push(git.commit_message(rev)?);
+    }
+
+    let revision = messages
+        .iter()
+        .find_map(|msg| find_trailer(msg, "Differential Revision"))
+        .ok_or_else(|| {
+            Error::new(ErrorKind::DifferentialRevisionNotFound {
+                rev: rev.to_string(),
+            })
+        })?;
+
+    if revision.starts_with("https://") || revision.starts_with("http://") {
+        Ok(revision.to_string())
+    } else {
+        Ok(format!("{}/{}", origin, revision))
+    }
+}
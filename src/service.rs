@@ -1,8 +1,11 @@
 use crate::async_runtime;
-use crate::config::Config;
+use crate::config::{Config, EnvConfig, HostConfig, HostKind, UrlTemplates};
 use crate::error::{Error, ErrorKind, Result};
+use crate::gitea_api;
 use crate::github_api::Client;
+use crate::gitlab_api;
 use crate::page::{DiffOp, Line, Page};
+use crate::probe;
 use crate::pull_request;
 use path_slash::PathExt as _;
 use std::borrow::Cow;
@@ -52,6 +55,37 @@ fn fetch_homepage(
     async_runtime::blocking(client.repo_homepage(user, repo))
 }
 
+fn fetch_gist_owner(id: &str, cfg: &Config) -> Result<Option<String>> {
+    let client = Client::build(
+        "api.github.com",
+        &cfg.env.github_token,
+        &cfg.env.https_proxy,
+    )?;
+    async_runtime::blocking(client.gist_owner(id))
+}
+
+fn fetch_gitea_website(
+    endpoint: &str,
+    token: &Option<impl AsRef<str>>,
+    https_proxy: &Option<impl AsRef<str>>,
+    user: &str,
+    repo: &str,
+) -> Result<Option<String>> {
+    let client = gitea_api::Client::build(endpoint, token, https_proxy)?;
+    let repo = async_runtime::blocking(client.repo(user, repo))?;
+    if repo.website.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(repo.website))
+    }
+}
+
+// Note: Page::FilePath falls back to a branch name when the commit does not exist in remote (#12).
+// Services which distinguish commits from branches in their URLs need to check it.
+fn is_commit_hash(rev: &str) -> bool {
+    rev.len() >= 7 && rev.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 fn check_slash_in_user(user: &str) -> Result<()> {
     if user.contains('/') {
         // Enter here because slug_from_path() allows '/' in user name to support GitLab's
@@ -66,21 +100,26 @@ fn check_slash_in_user(user: &str) -> Result<()> {
 }
 
 fn build_github_like_url(
-    host: &str,
+    host_cfg: &HostConfig,
     user: &str,
     repo: &str,
     api_endpoint: Option<impl AsRef<str>>,
     cfg: &Config,
     page: &Page,
 ) -> Result<String> {
+    let host = host_cfg.web_host();
+    let host = host.as_ref();
+    let base = host_cfg.web_base();
+    let base = base.as_ref();
+    let scheme = host_cfg.scheme();
     match page {
         Page::Open { website: true, .. } => {
-            match host {
-                "github.com" => {
+            match (host_cfg.kind, host) {
+                (HostKind::GitHub, "github.com") => {
                     if let Some(endpoint) = api_endpoint {
                         if let Ok(Some(homepage)) = fetch_homepage(
                             endpoint.as_ref(),
-                            cfg.env.github_token.as_deref(),
+                            host_cfg.token.as_deref(),
                             &cfg.env.https_proxy,
                             user,
                             repo,
@@ -91,15 +130,15 @@ fn build_github_like_url(
                     let host = &host[0..host.len() - 4];
                     Ok(format!("https://{}.{}.io/{}", user, host, repo))
                 }
-                "gitlab.com" => Ok(format!("https://{}.gitlab.io/{}", user, repo)),
-                host if host.starts_with("gitlab.") => {
-                    Ok(format!("https://{}.{}/{}", user, host, repo))
+                (HostKind::GitLab, "gitlab.com") => {
+                    Ok(format!("https://{}.gitlab.io/{}", user, repo))
                 }
-                // For GitHub Enterprise:
+                (HostKind::GitLab, host) => Ok(format!("https://{}.{}/{}", user, host, repo)),
+                // For GitHub Enterprise (Gitea is not built with this function):
                 //   https://help.github.com/enterprise/user/articles/user-organization-and-project-pages/
-                host => {
+                (_, host) => {
                     // Token is always required for GitHub Enterprise
-                    if let (Some(endpoint), Some(token)) = (&api_endpoint, &cfg.env.ghe_token) {
+                    if let (Some(endpoint), Some(token)) = (&api_endpoint, &host_cfg.token) {
                         if let Ok(Some(homepage)) = fetch_homepage(
                             endpoint.as_ref(),
                             Some(token),
@@ -126,6 +165,7 @@ fn build_github_like_url(
             if let Some(endpoint) = api_endpoint {
                 match async_runtime::blocking(pull_request::find_page(
                     endpoint.as_ref(),
+                    &host_cfg.token,
                     user,
                     repo,
                     cfg,
@@ -136,8 +176,8 @@ fn build_github_like_url(
                         repo,
                         branch,
                     } => Ok(format!(
-                        "https://{}/{}/{}/compare/{}?expand=1",
-                        host, author, repo, branch,
+                        "{}://{}/{}/{}/compare/{}?expand=1",
+                        scheme, base, author, repo, branch,
                     )),
                     pull_request::Page::NewAtParent {
                         author,
@@ -145,8 +185,8 @@ fn build_github_like_url(
                         fork_author,
                         branch,
                     } => Ok(format!(
-                        "https://{}/{}/{}/compare/{}:{}?expand=1",
-                        host, author, repo, fork_author, branch,
+                        "{}://{}/{}/{}/compare/{}:{}?expand=1",
+                        scheme, base, author, repo, fork_author, branch,
                     )),
                 }
             } else {
@@ -157,22 +197,25 @@ fn build_github_like_url(
         }
         Page::Open { .. } => {
             if let Some(b) = &cfg.branch {
-                Ok(format!("https://{}/{}/{}/tree/{}", host, user, repo, b))
+                Ok(format!(
+                    "{}://{}/{}/{}/tree/{}",
+                    scheme, base, user, repo, b
+                ))
             } else {
-                Ok(format!("https://{}/{}/{}", host, user, repo))
+                Ok(format!("{}://{}/{}/{}", scheme, base, user, repo))
             }
         }
         Page::Tag { tagname, .. } => Ok(format!(
-            "https://{}/{}/{}/tree/{}",
-            host, user, repo, tagname,
+            "{}://{}/{}/{}/tree/{}",
+            scheme, base, user, repo, tagname,
         )),
         Page::Diff { lhs, rhs, op } => Ok(format!(
-            "https://{}/{}/{}/compare/{}{}{}",
-            host, user, repo, lhs, op, rhs,
+            "{}://{}/{}/{}/compare/{}{}{}",
+            scheme, base, user, repo, lhs, op, rhs,
         )),
         Page::Commit { hash } => Ok(format!(
-            "https://{}/{}/{}/commit/{}",
-            host, user, repo, hash
+            "{}://{}/{}/{}/commit/{}",
+            scheme, base, user, repo, hash
         )),
         Page::FilePath {
             relative_path,
@@ -189,8 +232,9 @@ fn build_github_like_url(
                 "blob"
             };
             Ok(format!(
-                "https://{host}/{user}/{repo}/{feat}/{hash}/{path}{anchor}",
-                host = host,
+                "{scheme}://{base}/{user}/{repo}/{feat}/{hash}/{path}{anchor}",
+                scheme = scheme,
+                base = base,
                 user = user,
                 repo = repo,
                 feat = feat,
@@ -204,25 +248,414 @@ fn build_github_like_url(
             ))
         }
         Page::Issue { number } => Ok(format!(
-            "https://{}/{}/{}/issues/{}",
-            host, user, repo, number
+            "{}://{}/{}/{}/issues/{}",
+            scheme, base, user, repo, number
         )),
     }
 }
 
+fn build_hosted_url(
+    host_cfg: &HostConfig,
+    user: &str,
+    repo: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    match host_cfg.kind {
+        HostKind::GitHub => {
+            check_slash_in_user(user)?;
+            if let Some(repo) = repo.strip_suffix(".wiki") {
+                return build_github_wiki_url(host_cfg, user, repo, page);
+            }
+            let endpoint = host_cfg.api_endpoint();
+            build_github_like_url(host_cfg, user, repo, Some(endpoint), cfg, page)
+        }
+        HostKind::GitLab => build_gitlab_url(host_cfg, user, repo, cfg, page),
+        HostKind::Gitea => build_gitea_url(host_cfg, user, repo, cfg, page),
+        HostKind::Cgit | HostKind::GitWeb => {
+            build_plain_git_url(host_cfg, &format!("{}/{}", user, repo), cfg, page)
+        }
+    }
+}
+
+// cgit and GitWeb serve repositories at plain paths of clone URLs instead of '{user}/{repo}'
+fn build_plain_git_url(
+    host_cfg: &HostConfig,
+    repo: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    let root = format!("{}://{}", host_cfg.scheme(), host_cfg.web_base());
+    if host_cfg.kind == HostKind::GitWeb {
+        build_gitweb_url(&root, repo, cfg, page)
+    } else {
+        build_cgit_url(&root, repo, cfg, page)
+    }
+}
+
 fn build_gitlab_url(
-    host: &str,
+    host_cfg: &HostConfig,
     user: &str,
     repo: &str,
     cfg: &Config,
     page: &Page,
 ) -> Result<String> {
-    if let Page::Diff { op, .. } = page {
-        if *op == DiffOp::TwoDots {
+    // Note: Compare page shows changes since the merge base by default. 'straight=true' shows
+    // changes between two commits directly like '..'
+    if let Page::Diff {
+        lhs,
+        rhs,
+        op: DiffOp::TwoDots,
+    } = page
+    {
+        if !gitlab_supports_straight_diff(host_cfg, cfg) {
             return Error::err(ErrorKind::GitLabDiffNotSupported);
         }
+        return Ok(format!(
+            "{}://{}/{}/{}/compare/{}...{}?straight=true",
+            host_cfg.scheme(),
+            host_cfg.web_base(),
+            user,
+            repo,
+            lhs,
+            rhs,
+        ));
+    }
+    if let Page::Open {
+        website: false,
+        pull_request: true,
+    } = page
+    {
+        let project = format!("{}/{}", user, repo);
+        let page = async_runtime::blocking(pull_request::find_gitlab_page(
+            &host_cfg.api_endpoint(),
+            &host_cfg.token,
+            &project,
+            cfg,
+        ))?;
+        let base = format!("{}://{}", host_cfg.scheme(), host_cfg.web_base());
+        return Ok(gitlab_mr_page_url(&base, &project, page));
+    }
+    build_github_like_url(host_cfg, user, repo, Option::<&str>::None, cfg, page)
+}
+
+// Note: `base` is '{scheme}://{host}' of the GitLab instance
+pub fn gitlab_mr_page_url(base: &str, project: &str, page: pull_request::GitLabPage) -> String {
+    match page {
+        pull_request::GitLabPage::Existing { url } => url,
+        pull_request::GitLabPage::New {
+            branch,
+            target_project_id,
+        } => {
+            let mut url = format!(
+                "{}/{}/-/merge_requests/new?merge_request[source_branch]={}",
+                base, project, branch,
+            );
+            if let Some(id) = target_project_id {
+                url.push_str(&format!("&merge_request[target_project_id]={}", id));
+            }
+            url
+        }
+    }
+}
+
+// 'straight' mode of compare page was introduced at GitLab 13.9. Self-hosted instances may be older.
+// When the version cannot be fetched, assume it is supported. Version API requires a token.
+fn gitlab_supports_straight_diff(host_cfg: &HostConfig, cfg: &Config) -> bool {
+    if host_cfg.host == "gitlab.com" || host_cfg.token.is_none() {
+        return true;
+    }
+    let endpoint = host_cfg.api_endpoint();
+    let version = async_runtime::blocking(async {
+        gitlab_api::Client::build(&endpoint, &host_cfg.token, &cfg.env.https_proxy)?
+            .version()
+            .await
+    });
+    let version = match version {
+        Ok(v) => v,
+        Err(_) => return true,
+    };
+    let mut nums = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|s| s.parse::<u32>());
+    match (nums.next(), nums.next()) {
+        (Some(Ok(major)), Some(Ok(minor))) => (major, minor) >= (13, 9),
+        _ => true,
+    }
+}
+
+// Wiki of GitHub repository is cloned from '{repo}.wiki.git'. Each markup file is rendered as a
+// wiki page whose name is the file name without extension.
+//   https://docs.github.com/en/communities/documenting-your-project-with-wikis/adding-or-editing-wiki-pages
+fn build_github_wiki_url(
+    host_cfg: &HostConfig,
+    user: &str,
+    repo: &str,
+    page: &Page,
+) -> Result<String> {
+    let base = format!(
+        "{}://{}/{}/{}/wiki",
+        host_cfg.scheme(),
+        host_cfg.web_base(),
+        user,
+        repo,
+    );
+    match page {
+        Page::Open {
+            website: false,
+            pull_request: false,
+        } => Ok(base),
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::GitHubWikiNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => Error::err(ErrorKind::GitHubWikiNotSupported {
+            feature: "pull requests",
+        }),
+        Page::Commit { hash } => Ok(format!("{}/_compare/{}", base, hash)),
+        Page::FilePath { blame: true, .. } => {
+            Error::err(ErrorKind::GitHubWikiNotSupported { feature: "blame" })
+        }
+        Page::FilePath { is_dir: true, .. } => Error::err(ErrorKind::GitHubWikiNotSupported {
+            feature: "directories",
+        }),
+        Page::FilePath {
+            relative_path,
+            hash,
+            ..
+        } => {
+            let path = Path::new(relative_path);
+            let is_markup = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| WIKI_MARKUP_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+            let name = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(name) if is_markup => name,
+                _ => {
+                    return Error::err(ErrorKind::GitHubWikiNotSupported {
+                        feature: "files other than markup pages",
+                    })
+                }
+            };
+            // Note: Wiki pages are flat. Directories do not appear in URL
+            if is_commit_hash(hash) {
+                Ok(format!("{}/{}/{}", base, name, hash))
+            } else {
+                Ok(format!("{}/{}", base, name))
+            }
+        }
+        Page::Diff { .. } => Error::err(ErrorKind::GitHubWikiNotSupported {
+            feature: "diff between commits",
+        }),
+        Page::Tag { .. } => Error::err(ErrorKind::GitHubWikiNotSupported { feature: "tags" }),
+        Page::Issue { .. } => Error::err(ErrorKind::GitHubWikiNotSupported { feature: "issues" }),
+    }
+}
+
+const WIKI_MARKUP_EXTENSIONS: &[&str] = &[
+    "md",
+    "markdown",
+    "mediawiki",
+    "wiki",
+    "textile",
+    "rdoc",
+    "org",
+    "creole",
+    "rst",
+    "asciidoc",
+    "adoc",
+    "pod",
+];
+
+// Gist is cloned from 'gist.github.com/{id}.git' though its web page is at
+// 'gist.github.com/{user}/{id}'. Owner is fetched via API since it is not in the clone URL. Each
+// file in a gist is an anchor in the page.
+fn build_gist_url(path: &str, cfg: &Config, page: &Page) -> Result<String> {
+    let mut split = path.rsplit('/').filter(|s| !s.is_empty());
+    let id = split
+        .next()
+        .ok_or_else(|| {
+            Error::new(ErrorKind::NoRepoInPath {
+                path: path.to_string(),
+            })
+        })?
+        .trim_end_matches(".git");
+    let owner = match split.next() {
+        Some(user) => Some(user.to_string()),
+        None => fetch_gist_owner(id, cfg).ok().flatten(),
+    };
+    // Note: 'gist.github.com/{id}' is redirected to the page with owner
+    let base = match owner {
+        Some(owner) => format!("https://gist.github.com/{}/{}", owner, id),
+        None => format!("https://gist.github.com/{}", id),
+    };
+
+    match page {
+        Page::Open {
+            website: false,
+            pull_request: false,
+        } => Ok(base),
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::GistNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => Error::err(ErrorKind::GistNotSupported {
+            feature: "pull requests",
+        }),
+        // Note: Page at the revision is linked from 'revisions' page
+        Page::Commit { hash } => Ok(format!("{}/{}", base, hash)),
+        Page::FilePath { blame: true, .. } => {
+            Error::err(ErrorKind::GistNotSupported { feature: "blame" })
+        }
+        Page::FilePath { is_dir: true, .. } => Error::err(ErrorKind::GistNotSupported {
+            feature: "directories",
+        }),
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            ..
+        } => Ok(format!(
+            "{}{}#file-{}{}",
+            base,
+            if is_commit_hash(hash) {
+                Cow::Owned(format!("/{}", hash))
+            } else {
+                Cow::Borrowed("")
+            },
+            gist_file_anchor(relative_path),
+            match line {
+                None => "".to_string(),
+                Some(Line::At(line)) => format!("-L{}", line),
+                Some(Line::Range(start, end)) => format!("-L{}-L{}", start, end),
+            },
+        )),
+        Page::Diff { .. } => Error::err(ErrorKind::GistNotSupported {
+            feature: "diff between commits",
+        }),
+        Page::Tag { .. } => Error::err(ErrorKind::GistNotSupported { feature: "tags" }),
+        Page::Issue { .. } => Error::err(ErrorKind::GistNotSupported { feature: "issues" }),
+    }
+}
+
+// Note: Anchor is the file name in lower case where characters other than alphanumerics, '-' and
+// '_' are replaced with '-'. For example, 'Hello World.rs' is 'hello-world-rs'.
+fn gist_file_anchor(name: &str) -> String {
+    let mut anchor = String::with_capacity(name.len());
+    for c in name.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '_' {
+            c.to_ascii_lowercase()
+        } else {
+            '-'
+        };
+        if c != '-' || !anchor.ends_with('-') {
+            anchor.push(c);
+        }
+    }
+    anchor.trim_matches('-').to_string()
+}
+
+// Gitea and its fork Forgejo (e.g. https://codeberg.org) share the same URL structure and REST API:
+//   https://docs.gitea.com/usage/permalinks
+fn build_gitea_url(
+    host_cfg: &HostConfig,
+    user: &str,
+    repo: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    check_slash_in_user(user)?;
+    let endpoint = host_cfg.api_endpoint();
+    let token = &host_cfg.token;
+    let base = format!("{}://{}", host_cfg.scheme(), host_cfg.web_base());
+    match page {
+        Page::Open { website: true, .. } => {
+            if let Ok(Some(website)) =
+                fetch_gitea_website(&endpoint, token, &cfg.env.https_proxy, user, repo)
+            {
+                return Ok(website);
+            }
+            if host_cfg.host == "codeberg.org" {
+                // Codeberg Pages: https://docs.codeberg.org/codeberg-pages/
+                let with_repo = format!("https://{}.codeberg.page/{}", user, repo);
+                let without_repo = format!("https://{}.codeberg.page", user);
+                Ok(first_available_url(
+                    &mut [with_repo],
+                    without_repo,
+                    &cfg.env.https_proxy,
+                ))
+            } else {
+                Error::err(ErrorKind::WebsiteNotFound {
+                    repo: format!("{}/{}/{}", host_cfg.host, user, repo),
+                })
+            }
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => {
+            match async_runtime::blocking(pull_request::find_gitea_page(
+                &endpoint, token, user, repo, cfg,
+            ))? {
+                pull_request::Page::Existing { url } => Ok(url),
+                // Note: When only head branch is given, Gitea compares it with the default branch
+                pull_request::Page::New {
+                    author,
+                    repo,
+                    branch,
+                } => Ok(format!("{}/{}/{}/compare/{}", base, author, repo, branch,)),
+                pull_request::Page::NewAtParent {
+                    author,
+                    repo,
+                    fork_author,
+                    branch,
+                } => Ok(format!(
+                    "{}/{}/{}/compare/{}:{}",
+                    base, author, repo, fork_author, branch,
+                )),
+            }
+        }
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!("{}/{}/{}/src/branch/{}", base, user, repo, b))
+            } else {
+                Ok(format!("{}/{}/{}", base, user, repo))
+            }
+        }
+        Page::Tag { tagname, .. } => Ok(format!("{}/{}/{}/src/tag/{}", base, user, repo, tagname,)),
+        Page::Diff { lhs, rhs, op } => Ok(format!(
+            "{}/{}/{}/compare/{}{}{}",
+            base, user, repo, lhs, op, rhs,
+        )),
+        Page::Commit { hash } => Ok(format!("{}/{}/{}/commit/{}", base, user, repo, hash,)),
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            is_dir: _,
+        } => Ok(format!(
+            "{base}/{user}/{repo}/{feat}/{kind}/{hash}/{path}{anchor}",
+            base = base,
+            user = user,
+            repo = repo,
+            feat = if *blame { "blame" } else { "src" },
+            kind = if is_commit_hash(hash) {
+                "commit"
+            } else {
+                "branch"
+            },
+            hash = hash,
+            path = Path::new(relative_path).to_slash().unwrap(),
+            anchor = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) => format!("#L{}", line),
+                Some(Line::Range(start, end)) => format!("#L{}-L{}", start, end),
+            },
+        )),
+        Page::Issue { number } => Ok(format!("{}/{}/{}/issues/{}", base, user, repo, number,)),
     }
-    build_github_like_url(host, user, repo, Option::<&str>::None, cfg, page)
 }
 
 fn build_bitbucket_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
@@ -241,9 +674,10 @@ fn build_bitbucket_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Res
         }
         Page::Open {
             pull_request: true, ..
-        } => Error::err(ErrorKind::PullReqNotSupported {
-            service: "bitbucket.org".to_string(),
-        }),
+        } => {
+            let page = async_runtime::blocking(pull_request::find_bitbucket_page(user, repo, cfg))?;
+            Ok(bitbucket_pr_page_url(page))
+        }
         Page::Open { .. } => {
             if let Some(b) = &cfg.branch {
                 Ok(format!(
@@ -254,7 +688,15 @@ fn build_bitbucket_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Res
                 Ok(format!("https://bitbucket.org/{}/{}", user, repo))
             }
         }
-        Page::Diff { .. } => Error::err(ErrorKind::BitbucketDiffNotSupported),
+        // Note: Compare page shows changes since the merge base like '...'. Source and destination
+        // are separated with '\r' (%0D)
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::ThreeDots => Ok(format!(
+                "https://bitbucket.org/{}/{}/branches/compare/{}%0D{}",
+                user, repo, rhs, lhs,
+            )),
+            DiffOp::TwoDots => Error::err(ErrorKind::BitbucketDiffNotSupported),
+        },
         Page::Commit { hash } => Ok(format!(
             "https://bitbucket.org/{}/{}/commits/{}",
             user, repo, hash,
@@ -291,107 +733,1070 @@ fn build_bitbucket_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Res
     }
 }
 
-fn build_azure_devops_url(team: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
+pub fn bitbucket_pr_page_url(page: pull_request::Page) -> String {
+    match page {
+        pull_request::Page::Existing { url } => url,
+        pull_request::Page::New {
+            author,
+            repo,
+            branch,
+        }
+        | pull_request::Page::NewAtParent {
+            author,
+            repo,
+            branch,
+            ..
+        } => format!(
+            "https://bitbucket.org/{}/{}/pull-requests/new?source={}",
+            author, repo, branch,
+        ),
+    }
+}
+
+// Bitbucket Server (Bitbucket Data Center) is a self-hosted Bitbucket. Its URL structure is totally
+// different from Bitbucket Cloud:
+//   https://confluence.atlassian.com/bitbucketserver/
+// Note: `origin` is '{scheme}://{host}[:{port}]' since the server is often hosted with non-default
+// scheme or port like 'http://bitbucket.example.com:7990'
+fn build_bitbucket_server_url(
+    origin: &str,
+    project: &str,
+    repo: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    check_slash_in_user(project)?;
+    // Personal repositories belong to a project named '~{user}'
+    let (project, base) = match project.strip_prefix('~') {
+        Some(user) => (
+            project.to_string(),
+            format!("{}/users/{}/repos/{}", origin, user, repo),
+        ),
+        None => {
+            let key = project.to_uppercase();
+            let base = format!("{}/projects/{}/repos/{}", origin, key, repo);
+            (key, base)
+        }
+    };
     match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::BitbucketServerNotSupported { feature: "website" })
+        }
         Page::Open {
             pull_request: true, ..
         } => {
+            let endpoint = format!("{}/rest/api/1.0", origin);
+            let page = async_runtime::blocking(pull_request::find_bitbucket_server_page(
+                &endpoint, &project, repo, cfg,
+            ))?;
+            Ok(bitbucket_server_pr_page_url(&base, page))
+        }
+        Page::Open { .. } => {
             if let Some(b) = &cfg.branch {
-                Ok(format!("https://dev.azure.com/{}/_git/{}/pullrequestcreate?sourceRef={}&targetRef=master", team, repo, b))
+                Ok(format!("{}/browse?at=refs/heads/{}", base, b))
             } else {
-                Error::err(ErrorKind::NoLocalRepoFound {
-                    operation: "opening a pull request without specifying branch".to_string(),
-                })
+                Ok(format!("{}/browse", base))
             }
         }
+        Page::Tag { tagname, .. } => Ok(format!("{}/browse?at=refs/tags/{}", base, tagname)),
+        // Note: Compare page on Bitbucket Server shows changes since the merge base like '...'
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::ThreeDots => Ok(format!(
+                "{}/compare/diff?sourceBranch={}&targetBranch={}",
+                base, rhs, lhs,
+            )),
+            DiffOp::TwoDots => Error::err(ErrorKind::BitbucketServerNotSupported {
+                feature: "'..' for comparing diff between commits. Please use '...'",
+            }),
+        },
+        Page::Commit { hash } => Ok(format!("{}/commits/{}", base, hash)),
+        Page::FilePath { blame: true, .. } => {
+            Error::err(ErrorKind::BitbucketServerNotSupported { feature: "blame" })
+        }
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            ..
+        } => Ok(format!(
+            "{base}/browse/{path}?at={hash}{anchor}",
+            base = base,
+            path = Path::new(relative_path).to_slash().unwrap(),
+            hash = hash,
+            anchor = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) => format!("#{}", line),
+                Some(Line::Range(start, end)) => format!("#{}-{}", start, end),
+            },
+        )),
+        // Bitbucket Server does not have issue tracker. It is usually integrated with Jira.
+        Page::Issue { .. } => {
+            Error::err(ErrorKind::BitbucketServerNotSupported { feature: "issues" })
+        }
+    }
+}
+
+// Note: `base` is the URL of the repository like '{host}/projects/{key}/repos/{repo}'
+pub fn bitbucket_server_pr_page_url(base: &str, page: pull_request::Page) -> String {
+    match page {
+        pull_request::Page::Existing { url } => url,
+        pull_request::Page::New { branch, .. } | pull_request::Page::NewAtParent { branch, .. } => {
+            format!(
+                "{}/pull-requests?create&sourceBranch=refs/heads/{}",
+                base, branch,
+            )
+        }
+    }
+}
+
+// Repositories on Gerrit are browsed with Gitiles:
+//   https://gerrit.googlesource.com/gitiles/+/HEAD/Documentation/design.md
+// Note: `gitiles` and `gerrit` are base URLs like 'https://gerrit.example.com:8443/plugins/gitiles'
+fn build_gerrit_url(
+    gitiles: &str,
+    gerrit: &str,
+    project: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    let base = format!("{}/{}", gitiles, project);
+    match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::GerritNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => async_runtime::blocking(pull_request::find_gerrit_change_url(gerrit, project, cfg)),
         Page::Open { .. } => {
             if let Some(b) = &cfg.branch {
-                Ok(format!(
-                    "https://dev.azure.com/{}/_git/{}?version=GB{}",
-                    team, repo, b
-                ))
+                Ok(format!("{}/+log/refs/heads/{}", base, b))
             } else {
-                Ok(format!("https://dev.azure.com/{}/{}", team, repo))
+                Ok(base)
             }
         }
-        Page::Commit { hash } => Ok(format!(
-            "https://dev.azure.com/{}/_git/{}/commit/{}",
-            team, repo, hash
-        )),
-        Page::Tag { tagname, .. } => Ok(format!(
-            "https://dev.azure.com/{}/_git/{}?version=GT{}",
-            team, repo, tagname
-        )),
+        Page::Tag { tagname, .. } => Ok(format!("{}/+/refs/tags/{}", base, tagname)),
+        // Note: Gitiles only supports diff between two commits directly
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::TwoDots => Ok(format!("{}/+/{}..{}", base, lhs, rhs)),
+            DiffOp::ThreeDots => Error::err(ErrorKind::GerritNotSupported {
+                feature: "'...' for comparing diff between commits. Please use '..'",
+            }),
+        },
+        Page::Commit { hash } => Ok(format!("{}/+/{}", base, hash)),
         Page::FilePath {
             relative_path,
             hash,
-            line: None,
+            line,
             blame,
             is_dir: _,
         } => Ok(format!(
-            "https://dev.azure.com/{}/_git/{}/commit/{}?path={}{}",
-            team,
-            repo,
-            hash,
-            Path::new(relative_path).to_slash().unwrap(),
-            if *blame { "?_a=annotate" } else { "" },
-        )),
-        Page::Issue { number } => Ok(format!(
-            "https://dev.azure.com/{}/{}/_workitems/edit/{}",
-            team, repo, number
+            "{base}/{feat}/{hash}/{path}{anchor}",
+            base = base,
+            feat = if *blame { "+blame" } else { "+" },
+            hash = hash,
+            path = Path::new(relative_path).to_slash().unwrap(),
+            // Note: Gitiles does not support range of lines
+            anchor = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) | Some(Line::Range(line, _)) => format!("#{}", line),
+            },
         )),
-        _ => Error::err(ErrorKind::AzureDevOpsNotSupported),
+        Page::Issue { .. } => Error::err(ErrorKind::GerritNotSupported { feature: "issues" }),
     }
 }
 
-fn is_azure_devops_host(host: &str) -> bool {
-    [
-        "visualstudio.com",
-        "vs-ssh.visualstudio.com",
-        "dev.azure.com",
-        "ssh.dev.azure.com",
-    ]
-    .contains(&host)
+// cgit serves web pages at the same path as its clone URL:
+//   https://git.zx2c4.com/cgit/about/
+// Note: `root` is the URL where cgit is served like 'https://git.example.com'
+fn build_cgit_url(root: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
+    let base = format!("{}/{}", root, repo);
+    match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::CgitNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => Error::err(ErrorKind::PullReqNotSupported {
+            service: root.to_string(),
+        }),
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!("{}/log/?h={}", base, b))
+            } else {
+                Ok(base)
+            }
+        }
+        Page::Tag { tagname, .. } => Ok(format!("{}/tag/?h={}", base, tagname)),
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::TwoDots => Ok(format!("{}/diff/?id={}&id2={}", base, rhs, lhs)),
+            DiffOp::ThreeDots => Error::err(ErrorKind::CgitNotSupported {
+                feature: "'...' for comparing diff between commits. Please use '..'",
+            }),
+        },
+        Page::Commit { hash } => Ok(format!("{}/commit/?id={}", base, hash)),
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            is_dir: _,
+        } => Ok(format!(
+            "{base}/{feat}/{path}?id={hash}{anchor}",
+            base = base,
+            feat = if *blame { "blame" } else { "tree" },
+            path = Path::new(relative_path).to_slash().unwrap(),
+            hash = hash,
+            // Note: cgit does not support range of lines
+            anchor = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) | Some(Line::Range(line, _)) => format!("#n{}", line),
+            },
+        )),
+        Page::Issue { .. } => Error::err(ErrorKind::CgitNotSupported { feature: "issues" }),
+    }
 }
 
-// Note: Parse '/team/_git/repo' or '/team/repo' into 'team' and 'repo'
-pub fn azure_devops_slug_from_path(path: &str) -> Result<(&str, &str)> {
-    let mut split = path.split('/').skip_while(|s| s.is_empty());
-
-    let mut team = split.next().ok_or_else(|| {
-        Error::new(ErrorKind::NoUserInPath {
-            path: path.to_string(),
-        })
-    })?;
+// GitWeb selects a page with 'a' (action) parameter of query:
+//   https://git-scm.com/docs/gitweb#_actions_and_urls
+// Note: `root` is the URL where GitWeb is served like 'https://git.example.com'
+fn build_gitweb_url(root: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
+    let base = format!("{}/?p={}", root, repo);
+    match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::GitWebNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => Error::err(ErrorKind::PullReqNotSupported {
+            service: root.to_string(),
+        }),
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!("{};a=shortlog;h=refs/heads/{}", base, b))
+            } else {
+                Ok(format!("{};a=summary", base))
+            }
+        }
+        // Note: 'tag' action is only available for annotated tags
+        Page::Tag { tagname, .. } => Ok(format!("{};a=shortlog;h=refs/tags/{}", base, tagname)),
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::TwoDots => Ok(format!("{};a=commitdiff;h={};hp={}", base, rhs, lhs)),
+            DiffOp::ThreeDots => Error::err(ErrorKind::GitWebNotSupported {
+                feature: "'...' for comparing diff between commits. Please use '..'",
+            }),
+        },
+        Page::Commit { hash } => Ok(format!("{};a=commit;h={}", base, hash)),
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            is_dir,
+        } => Ok(format!(
+            "{base};a={action};f={path};hb={hash}{anchor}",
+            base = base,
+            action = if *blame {
+                "blame"
+            } else if *is_dir {
+                "tree"
+            } else {
+                "blob"
+            },
+            path = Path::new(relative_path).to_slash().unwrap(),
+            hash = hash,
+            // Note: GitWeb does not support range of lines
+            anchor = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) | Some(Line::Range(line, _)) => format!("#l{}", line),
+            },
+        )),
+        Page::Issue { .. } => Error::err(ErrorKind::GitWebNotSupported { feature: "issues" }),
+    }
+}
 
-    // Strip off v3 from Azure DevOps ssh:// paths.
-    // See: preprocess_repo_to_url
-    //
-    // Example: ssh://git@ssh.dev.azure.com:v3/team/repo/repo
-    //
-    if team == "v3" {
-        team = split.next().ok_or_else(|| {
-            Error::new(ErrorKind::NoRepoInPath {
-                path: path.to_string(),
+// Phabricator Diffusion routes repositories by short name ('/source/{name}') or by callsign
+// ('/diffusion/{callsign}'):
+//   https://secure.phabricator.com/book/phabricator/article/diffusion_uris/
+fn build_phabricator_url(
+    origin: &str,
+    kind: &str,
+    id: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    let base = format!("{}/{}/{}", origin, kind, id);
+    match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::PhabricatorNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => pull_request::find_differential_revision_url(origin, cfg),
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!("{}/browse/{}/", base, b))
+            } else {
+                Ok(format!("{}/", base))
+            }
+        }
+        Page::Tag { tagname, .. } => Ok(format!("{}/browse/{}/", base, tagname)),
+        // Note: Diffusion compares commits reachable from head but not from against
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::ThreeDots => Ok(format!("{}/compare/?head={}&against={}", base, rhs, lhs)),
+            DiffOp::TwoDots => Error::err(ErrorKind::PhabricatorNotSupported {
+                feature: "'..' for comparing diff between commits. Please use '...'",
+            }),
+        },
+        Page::Commit { hash } => Ok(format!("{}/commit/{}/", base, hash)),
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            is_dir: _,
+        } => {
+            // Note: Branch is mandatory in browse URL. Commit is pinned with ';{commit}'
+            let (branch, commit) = if is_commit_hash(hash) {
+                let branch = match &cfg.branch {
+                    Some(b) => b.clone(),
+                    None => cfg.git().current_branch()?,
+                };
+                (branch, format!(";{}", hash))
+            } else {
+                (hash.clone(), "".to_string())
+            };
+            Ok(format!(
+                "{base}/browse/{branch}/{path}{commit}{lines}{blame}",
+                base = base,
+                branch = branch,
+                path = Path::new(relative_path).to_slash().unwrap(),
+                commit = commit,
+                lines = match line {
+                    None => "".to_string(),
+                    Some(Line::At(line)) => format!("${}", line),
+                    Some(Line::Range(start, end)) => format!("${}-{}", start, end),
+                },
+                blame = if *blame { "?blame=1" } else { "" },
+            ))
+        }
+        // Note: Maniphest tasks are not tied to repositories
+        Page::Issue { number } => Ok(format!("{}/T{}", origin, number)),
+    }
+}
+
+// Pagure is used for Fedora (src.fedoraproject.org) and pagure.io:
+//   https://docs.pagure.org/pagure/usage/index.html
+fn build_pagure_url(origin: &str, project: &str, cfg: &Config, page: &Page) -> Result<String> {
+    let base = format!("{}/{}", origin, project);
+    match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::PagureNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => match async_runtime::blocking(pull_request::find_pagure_page(origin, project, cfg))? {
+            pull_request::Page::Existing { url } => Ok(url),
+            pull_request::Page::New { branch, .. }
+            | pull_request::Page::NewAtParent { branch, .. } => {
+                let remote = cfg.remote.as_deref().unwrap_or("origin");
+                match cfg.git().remote_default_branch(remote) {
+                    Ok(default) => Ok(format!("{}/diff/{}..{}", base, default, branch)),
+                    // Note: The list of pull requests has a button to open a new pull request
+                    Err(_) => Ok(format!("{}/pull-requests", base)),
+                }
+            }
+        },
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!("{}/tree/{}", base, b))
+            } else {
+                Ok(base)
+            }
+        }
+        Page::Tag { tagname, .. } => Ok(format!("{}/tree/{}", base, tagname)),
+        // Note: Pagure only compares a branch with its merge base like pull requests
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::ThreeDots => Ok(format!("{}/diff/{}..{}", base, lhs, rhs)),
+            DiffOp::TwoDots => Error::err(ErrorKind::PagureNotSupported {
+                feature: "'..' for comparing diff between commits. Please use '...'",
+            }),
+        },
+        Page::Commit { hash } => Ok(format!("{}/c/{}", base, hash)),
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            is_dir: _,
+        } => {
+            let path = Path::new(relative_path).to_slash().unwrap();
+            // Note: Pagure does not support range of lines
+            let anchor = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) | Some(Line::Range(line, _)) => format!("#_{}", line),
+            };
+            if *blame {
+                Ok(format!(
+                    "{}/blame/{}?identifier={}{}",
+                    base, path, hash, anchor,
+                ))
+            } else {
+                Ok(format!("{}/blob/{}/f/{}{}", base, hash, path, anchor))
+            }
+        }
+        Page::Issue { number } => Ok(format!("{}/issue/{}", base, number)),
+    }
+}
+
+// Git repositories on Launchpad are browsed with cgit at git.launchpad.net. Merge proposals and
+// bugs are on code.launchpad.net and bugs.launchpad.net.
+//   https://help.launchpad.net/Code/Git
+fn build_launchpad_url(repo: &str, cfg: &Config, page: &Page) -> Result<String> {
+    match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::LaunchpadNotSupported { feature: "website" })
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => {
+            if repo.contains("/+git/") {
+                let branch = match &cfg.branch {
+                    Some(b) => b.clone(),
+                    None => cfg.git().current_branch()?,
+                };
+                Ok(format!(
+                    "https://code.launchpad.net/{}/+ref/{}",
+                    repo, branch,
+                ))
+            } else {
+                // Note: Repository path without '+git' is a shortcut to the default repository
+                // of the project or the package
+                Ok(format!(
+                    "https://code.launchpad.net/{}/+activereviews",
+                    repo
+                ))
+            }
+        }
+        Page::Diff {
+            op: DiffOp::ThreeDots,
+            ..
+        } => Error::err(ErrorKind::LaunchpadNotSupported {
+            feature: "'...' for comparing diff between commits. Please use '..'",
+        }),
+        // Note: Bug numbers are unique across all projects on Launchpad
+        Page::Issue { number } => Ok(format!("https://bugs.launchpad.net/bugs/{}", number)),
+        _ => build_cgit_url("https://git.launchpad.net", repo, cfg, page),
+    }
+}
+
+// Note: Placeholder '{name}' in template is replaced with the value of the variable. Placeholder
+// which is not available for the page is an error since it is usually a typo.
+fn expand_url_template(template: &str, vars: &[(&str, &str)]) -> Result<String> {
+    let broken = |msg: String| {
+        Error::err(ErrorKind::BrokenUrlTemplate {
+            template: template.to_string(),
+            msg,
+        })
+    };
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = match after.find('}') {
+            Some(end) => end,
+            None => return broken("'{' is not closed with '}'".to_string()),
+        };
+        let name = &after[..end];
+        match vars.iter().find(|(k, _)| *k == name) {
+            Some((_, v)) => expanded.push_str(v),
+            None => return broken(format!("Placeholder {{{}}} is not available", name)),
+        }
+        rest = &after[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+// User-defined hosting service in config file. `project` is split into '{user}' and '{repo}' at
+// the last '/' so that '{user}' may contain namespaces.
+fn build_templated_url<'t>(
+    host: &str,
+    templates: &'t UrlTemplates,
+    project: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    let template = |t: &'t Option<String>, name: &'static str| -> Result<&'t str> {
+        t.as_deref().ok_or_else(|| {
+            Error::new(ErrorKind::UrlTemplateNotFound {
+                host: templates.host.clone(),
+                template: name,
             })
-        })?;
+        })
+    };
+    let (user, repo) = match project.rsplit_once('/') {
+        Some((user, repo)) => (Some(user), repo),
+        None => (None, project),
+    };
+    let mut vars = vec![("host", host), ("repo", repo)];
+    if let Some(user) = user {
+        vars.push(("user", user));
+    }
+    // Note: '{user}' is not available when the path has only one component like '/repo.git'
+    let expand = |t: &str, vars: &[(&str, &str)]| {
+        if user.is_none() && t.contains("{user}") {
+            return Error::err(ErrorKind::NoUserInPath {
+                path: project.to_string(),
+            });
+        }
+        expand_url_template(t, vars)
+    };
+
+    match page {
+        Page::Open { website: true, .. } => expand(template(&templates.website, "website")?, &vars),
+        Page::Open {
+            pull_request: true, ..
+        } => {
+            let branch = match &cfg.branch {
+                Some(b) => b.clone(),
+                None => cfg.git().current_branch()?,
+            };
+            vars.push(("branch", &branch));
+            expand(template(&templates.pull_request, "pull_request")?, &vars)
+        }
+        Page::Open { .. } => match &cfg.branch {
+            Some(b) => {
+                vars.push(("branch", b));
+                expand(template(&templates.branch, "branch")?, &vars)
+            }
+            None => expand(template(&templates.repo, "repo")?, &vars),
+        },
+        Page::Tag { tagname, commit } => {
+            vars.push(("tag", tagname));
+            vars.push(("hash", commit));
+            expand(template(&templates.tag, "tag")?, &vars)
+        }
+        Page::Diff { lhs, rhs, op } => {
+            vars.push(("lhs", lhs));
+            vars.push(("rhs", rhs));
+            let t = match op {
+                DiffOp::TwoDots => template(&templates.diff_two_dots, "diff_two_dots")?,
+                DiffOp::ThreeDots => template(&templates.diff_three_dots, "diff_three_dots")?,
+            };
+            expand(t, &vars)
+        }
+        Page::Commit { hash } => {
+            vars.push(("hash", hash));
+            expand(template(&templates.commit, "commit")?, &vars)
+        }
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            is_dir,
+        } => {
+            let path = Path::new(relative_path).to_slash().unwrap();
+            vars.push(("hash", hash));
+            vars.push(("path", &path));
+            let t = if *blame {
+                template(&templates.blame, "blame")?
+            } else if *is_dir && templates.dir.is_some() {
+                template(&templates.dir, "dir")?
+            } else {
+                template(&templates.file, "file")?
+            };
+            let mut url = expand(t, &vars)?;
+
+            let (start, end) = match line {
+                None => return Ok(url),
+                Some(Line::At(line)) => (line.to_string(), line.to_string()),
+                Some(Line::Range(start, end)) => (start.to_string(), end.to_string()),
+            };
+            vars.push(("line_start", &start));
+            vars.push(("line_end", &end));
+            // Note: When `line_range` is not defined, `line` is used with the start line
+            let t = match (line, &templates.line_range) {
+                (Some(Line::Range(..)), Some(t)) => t.as_str(),
+                _ => template(&templates.line, "line")?,
+            };
+            url.push_str(&expand(t, &vars)?);
+            Ok(url)
+        }
+        Page::Issue { number } => {
+            let number = number.to_string();
+            vars.push(("number", &number));
+            expand(template(&templates.issue, "issue")?, &vars)
+        }
+    }
+}
+
+// AWS CodeCommit does not have its own web frontend. Repositories are browsed on AWS console.
+//   https://docs.aws.amazon.com/codecommit/latest/userguide/welcome.html
+fn build_codecommit_url(region: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
+    let base = format!(
+        "https://{}.console.aws.amazon.com/codesuite/codecommit/repositories/{}",
+        region, repo,
+    );
+    match page {
+        Page::Open { website: true, .. } => {
+            Error::err(ErrorKind::CodeCommitNotSupported { feature: "website" })
+        }
+        // Note: Pull requests cannot be searched without signing requests with AWS credentials.
+        // Open the list of open pull requests instead.
+        Page::Open {
+            pull_request: true, ..
+        } => Ok(format!(
+            "{}/pull-requests?region={}&status=OPEN",
+            base, region,
+        )),
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!(
+                    "{}/browse/refs/heads/{}?region={}",
+                    base, b, region,
+                ))
+            } else {
+                Ok(format!("{}/browse?region={}", base, region))
+            }
+        }
+        Page::Tag { tagname, .. } => Ok(format!(
+            "{}/browse/refs/tags/{}?region={}",
+            base, tagname, region,
+        )),
+        // Note: Compare page on AWS console shows changes since the merge base like '...'
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::ThreeDots => Ok(format!(
+                "{}/compare/{}/.../{}?region={}",
+                base, lhs, rhs, region,
+            )),
+            DiffOp::TwoDots => Error::err(ErrorKind::CodeCommitNotSupported {
+                feature: "'..' for comparing diff between commits. Please use '...'",
+            }),
+        },
+        Page::Commit { hash } => Ok(format!("{}/commit/{}?region={}", base, hash, region)),
+        Page::FilePath { blame: true, .. } => {
+            Error::err(ErrorKind::CodeCommitNotSupported { feature: "blame" })
+        }
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            ..
+        } => Ok(format!(
+            "{base}/browse/{rev}/--/{path}?region={region}{lines}",
+            base = base,
+            rev = if is_commit_hash(hash) {
+                Cow::Borrowed(hash.as_str())
+            } else {
+                Cow::Owned(format!("refs/heads/{}", hash))
+            },
+            path = Path::new(relative_path).to_slash().unwrap(),
+            region = region,
+            lines = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) => format!("&lines={}-{}", line, line),
+                Some(Line::Range(start, end)) => format!("&lines={}-{}", start, end),
+            },
+        )),
+        Page::Issue { .. } => Error::err(ErrorKind::CodeCommitNotSupported { feature: "issues" }),
+    }
+}
+
+// SourceHut consists of several services. Repositories are on git.sr.ht, issues are tracked on
+// todo.sr.ht and patches are sent to mailing lists on lists.sr.ht. Owner names are prefixed with '~'.
+//   https://man.sr.ht/
+fn build_sourcehut_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
+    check_slash_in_user(user)?;
+    match page {
+        Page::Open { website: true, .. } => {
+            // SourceHut pages: https://srht.site/
+            let with_repo = format!("https://{}.srht.site/{}", user, repo);
+            let without_repo = format!("https://{}.srht.site", user);
+            Ok(first_available_url(
+                &mut [with_repo],
+                without_repo,
+                &cfg.env.https_proxy,
+            ))
+        }
+        Page::Open {
+            pull_request: true, ..
+        } => {
+            // SourceHut has no pull request. Patches are sent to a mailing list. Projects usually
+            // configure the list address to `sendemail.to` (e.g. ~user/repo-devel@lists.sr.ht).
+            // When it is not found, assume a list with the same name as the repository.
+            let list = cfg
+                .git()
+                .command(&["config", "--get", "sendemail.to"])
+                .ok()
+                .and_then(|to| {
+                    let list = to.strip_suffix("@lists.sr.ht")?.strip_prefix('~')?;
+                    let (owner, name) = list.split_once('/')?;
+                    Some(format!("{}/{}", owner, name))
+                })
+                .unwrap_or_else(|| format!("{}/{}", user, repo));
+            Ok(format!("https://lists.sr.ht/~{}/patches", list))
+        }
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!("https://git.sr.ht/~{}/{}/log/{}", user, repo, b))
+            } else {
+                Ok(format!("https://git.sr.ht/~{}/{}", user, repo))
+            }
+        }
+        Page::Tag { tagname, .. } => Ok(format!(
+            "https://git.sr.ht/~{}/{}/refs/{}",
+            user, repo, tagname,
+        )),
+        Page::Diff { .. } => Error::err(ErrorKind::SourceHutDiffNotSupported),
+        Page::Commit { hash } => Ok(format!(
+            "https://git.sr.ht/~{}/{}/commit/{}",
+            user, repo, hash,
+        )),
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            is_dir: _,
+        } => {
+            let path = Path::new(relative_path).to_slash().unwrap();
+            let anchor = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) => format!("#L{}", line),
+                Some(Line::Range(start, end)) => format!("#L{}-{}", start, end),
+            };
+            if *blame {
+                Ok(format!(
+                    "https://git.sr.ht/~{}/{}/blame/{}/{}{}",
+                    user, repo, hash, path, anchor,
+                ))
+            } else {
+                Ok(format!(
+                    "https://git.sr.ht/~{}/{}/tree/{}/item/{}{}",
+                    user, repo, hash, path, anchor,
+                ))
+            }
+        }
+        Page::Issue { number } => Ok(format!("https://todo.sr.ht/~{}/{}/{}", user, repo, number,)),
     }
+}
 
-    let mut repo = split.next().ok_or_else(|| {
+// Azure DevOps has three levels of organization, project and repository. A project can contain
+// multiple repositories. `org_url` is 'https://dev.azure.com/{org}' or legacy
+// 'https://{org}.visualstudio.com'.
+fn build_azure_devops_url(
+    org_url: &str,
+    org: &str,
+    project: &str,
+    repo: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Result<String> {
+    let repo_url = format!("{}/{}/_git/{}", org_url, project, repo);
+    match page {
+        Page::Open {
+            pull_request: true, ..
+        } => {
+            let page = async_runtime::blocking(pull_request::find_azure_devops_page(
+                org, project, repo, cfg,
+            ))?;
+            Ok(azure_devops_pr_page_url(&repo_url, page))
+        }
+        Page::Open { .. } => {
+            if let Some(b) = &cfg.branch {
+                Ok(format!("{}?version=GB{}", repo_url, b))
+            } else {
+                Ok(repo_url)
+            }
+        }
+        Page::Commit { hash } => Ok(format!("{}/commit/{}", repo_url, hash)),
+        Page::Tag { tagname, .. } => Ok(format!("{}?version=GT{}", repo_url, tagname)),
+        // Note: Compare page shows changes since the merge base like '...'
+        Page::Diff { lhs, rhs, op } => match op {
+            DiffOp::ThreeDots => Ok(format!(
+                "{}/branchCompare?baseVersion=GC{}&targetVersion=GC{}",
+                repo_url, lhs, rhs,
+            )),
+            DiffOp::TwoDots => Error::err(ErrorKind::AzureDevOpsNotSupported {
+                feature: "'..' for comparing diff between commits. Please use '...'",
+            }),
+        },
+        Page::FilePath {
+            relative_path,
+            hash,
+            line,
+            blame,
+            ..
+        } => Ok(format!(
+            "{repo_url}?path=/{path}&version={version}{lines}{action}",
+            repo_url = repo_url,
+            path = Path::new(relative_path).to_slash().unwrap(),
+            version = if is_commit_hash(hash) {
+                format!("GC{}", hash)
+            } else {
+                format!("GB{}", hash)
+            },
+            lines = match line {
+                None => "".to_string(),
+                Some(Line::At(line)) => format!(
+                    "&line={}&lineEnd={}&lineStartColumn=1&lineEndColumn=1&lineStyle=plain",
+                    line, line,
+                ),
+                Some(Line::Range(start, end)) => format!(
+                    "&line={}&lineEnd={}&lineStartColumn=1&lineEndColumn=1&lineStyle=plain",
+                    start, end,
+                ),
+            },
+            action = if *blame { "&_a=blame" } else { "" },
+        )),
+        Page::Issue { number } => Ok(format!(
+            "{}/{}/_workitems/edit/{}",
+            org_url, project, number
+        )),
+    }
+}
+
+// Note: `repo_url` is '{org_url}/{project}/_git/{repo}'
+pub fn azure_devops_pr_page_url(repo_url: &str, page: pull_request::AzureDevOpsPage) -> String {
+    match page {
+        pull_request::AzureDevOpsPage::Existing { id } => {
+            format!("{}/pullrequest/{}", repo_url, id)
+        }
+        pull_request::AzureDevOpsPage::New { branch, target } => {
+            let mut url = format!("{}/pullrequestcreate?sourceRef={}", repo_url, branch);
+            // Note: When target is omitted, default branch is selected on the page
+            if let Some(target) = target {
+                url.push_str(&format!("&targetRef={}", target));
+            }
+            url
+        }
+    }
+}
+
+fn is_bitbucket_server_host(host: &str, env: &EnvConfig) -> bool {
+    match &env.bitbucket_server_url_host {
+        Some(h) if host == h => true,
+        _ => host.starts_with("bitbucket.") && host != "bitbucket.org",
+    }
+}
+
+fn is_azure_devops_host(host: &str) -> bool {
+    ["visualstudio.com", "dev.azure.com", "ssh.dev.azure.com"].contains(&host)
+        || host.ends_with(".visualstudio.com")
+}
+
+// Note: Parse a host and a path into organization, project and repository. Supported forms are:
+//   dev.azure.com/{org}/{project}/_git/{repo}
+//   dev.azure.com/{org}/_git/{repo} (project has the same name as the repository)
+//   dev.azure.com/{org}/{project} (repository has the same name as the project)
+//   {org}.visualstudio.com/[DefaultCollection/]{project}/_git/{repo}
+//   ssh.dev.azure.com:v3/{org}/{project}/{repo}
+//   vs-ssh.visualstudio.com:v3/{org}/{project}/{repo}
+pub fn azure_devops_repo_from_path<'a>(
+    host: &'a str,
+    path: &'a str,
+) -> Result<(&'a str, &'a str, &'a str)> {
+    let no_repo = || {
         Error::new(ErrorKind::NoRepoInPath {
             path: path.to_string(),
         })
-    })?;
+    };
+    let mut split = path.split('/').filter(|s| !s.is_empty());
 
-    if repo.ends_with("_git") {
-        repo = split.next().ok_or_else(|| {
-            Error::new(ErrorKind::NoRepoInPath {
+    let org = match host.strip_suffix(".visualstudio.com") {
+        Some(org) if org != "vs-ssh" => org,
+        _ => split.next().ok_or_else(|| {
+            Error::new(ErrorKind::NoUserInPath {
                 path: path.to_string(),
             })
-        })?;
+        })?,
+    };
+
+    // Strip off v3 from Azure DevOps ssh:// paths.
+    // See: preprocess_repo_to_url
+    //
+    // Example: ssh://git@ssh.dev.azure.com:v3/org/project/repo
+    //
+    if org == "v3" {
+        let org = split.next().ok_or_else(no_repo)?;
+        let project = split.next().ok_or_else(no_repo)?;
+        let repo = split.next().ok_or_else(no_repo)?.trim_end_matches(".git");
+        return Ok((org, project, repo));
+    }
+
+    let mut project = split.next().ok_or_else(no_repo)?;
+    if project == "DefaultCollection" {
+        project = split.next().ok_or_else(no_repo)?;
+    }
+    if project == "_git" {
+        let repo = split.next().ok_or_else(no_repo)?.trim_end_matches(".git");
+        return Ok((org, repo, repo));
+    }
+
+    let repo = match split.next() {
+        Some("_git") => split.next().ok_or_else(no_repo)?.trim_end_matches(".git"),
+        _ => project,
+    };
+    Ok((org, project, repo))
+}
+
+// Note: Returns Gitiles base and Gerrit host. googlesource.com serves Gitiles at
+// '{name}.googlesource.com' and Gerrit at '{name}-review.googlesource.com'. Self-hosted Gerrit
+// serves Gitiles as a plugin.
+fn gerrit_hosts(host: &str, origin: &str, env: &EnvConfig) -> Option<(String, String)> {
+    if let Some(name) = host.strip_suffix(".googlesource.com") {
+        let name = name.strip_suffix("-review").unwrap_or(name);
+        return Some((
+            format!("https://{}.googlesource.com", name),
+            format!("https://{}-review.googlesource.com", name),
+        ));
+    }
+    let is_gerrit = match &env.gerrit_url_host {
+        Some(h) if host == h => true,
+        _ => host.starts_with("gerrit."),
+    };
+    if is_gerrit {
+        Some((format!("{}/plugins/gitiles", origin), origin.to_string()))
+    } else {
+        None
+    }
+}
+
+// Note: Parse '/a/project/name' or '/project/name.git' into 'project/name'. Gerrit project name
+// may contain '/' and '/a/' is a prefix for authenticated access.
+pub fn gerrit_project_from_path(path: &str) -> Result<&str> {
+    let trimmed = path.trim_matches('/');
+    let project = trimmed
+        .strip_prefix("a/")
+        .unwrap_or(trimmed)
+        .trim_end_matches(".git");
+    if project.is_empty() {
+        Err(Error::new(ErrorKind::NoRepoInPath {
+            path: path.to_string(),
+        }))
+    } else {
+        Ok(project)
+    }
+}
+
+// Note: cgit and GitWeb do not have any convention for user name in path. Whole path is used as
+// repository name.
+fn plain_repo_from_path(path: &str) -> Result<&str> {
+    let repo = path.trim_matches('/');
+    if repo.is_empty() {
+        Err(Error::new(ErrorKind::NoRepoInPath {
+            path: path.to_string(),
+        }))
+    } else {
+        Ok(repo)
+    }
+}
+
+fn is_phabricator_host(host: &str, env: &EnvConfig) -> bool {
+    match &env.phabricator_url_host {
+        Some(h) if host == h => true,
+        _ => host.starts_with("phabricator."),
+    }
+}
+
+// Note: Parse '/source/{name}.git' or '/diffusion/{callsign}/{name}.git' into ('source', name) or
+// ('diffusion', callsign)
+pub fn phabricator_repo_from_path(path: &str) -> Result<(&str, &str)> {
+    let mut split = path.trim_matches('/').split('/');
+    match (split.next(), split.next()) {
+        (Some(kind @ "source"), Some(name)) => {
+            let name = name.trim_end_matches(".git");
+            if !name.is_empty() {
+                return Ok((kind, name));
+            }
+        }
+        (Some(kind @ "diffusion"), Some(callsign)) if !callsign.is_empty() => {
+            return Ok((kind, callsign));
+        }
+        _ => {}
     }
+    Err(Error::new(ErrorKind::NoRepoInPath {
+        path: path.to_string(),
+    }))
+}
 
-    Ok((team, repo))
+// Note: pkgs.fedoraproject.org is a host for cloning via SSH
+fn pagure_host<'a>(host: &'a str, env: &EnvConfig) -> Option<&'a str> {
+    match host {
+        "pagure.io" | "src.fedoraproject.org" => Some(host),
+        "pkgs.fedoraproject.org" => Some("src.fedoraproject.org"),
+        _ if env.pagure_url_host.as_deref() == Some(host) => Some(host),
+        _ => None,
+    }
+}
+
+// Note: Pagure and Launchpad have namespaced paths such as 'rpms/{name}' or '~user/+git/{repo}'.
+// Whole path is used as project name.
+pub fn project_path_from_path(path: &str) -> Result<&str> {
+    let project = path.trim_matches('/').trim_end_matches(".git");
+    if project.is_empty() {
+        Err(Error::new(ErrorKind::NoRepoInPath {
+            path: path.to_string(),
+        }))
+    } else {
+        Ok(project)
+    }
+}
+
+// Note: Extract region from 'git-codecommit.{region}.amazonaws.com'. FIPS endpoints are
+// 'git-codecommit-fips.{region}.amazonaws.com'.
+fn codecommit_region(host: &str) -> Option<&str> {
+    let region = host
+        .strip_prefix("git-codecommit.")
+        .or_else(|| host.strip_prefix("git-codecommit-fips."))?
+        .strip_suffix(".amazonaws.com")?;
+    if region.is_empty() || region.contains('.') {
+        None
+    } else {
+        Some(region)
+    }
+}
+
+// Note: Parse '/v1/repos/repo' into 'repo'
+pub fn codecommit_repo_from_path(path: &str) -> Result<&str> {
+    let path = path.trim_start_matches('/');
+    match path.strip_prefix("v1/repos/") {
+        Some(repo) => {
+            let repo = repo.trim_end_matches('/').trim_end_matches(".git");
+            if repo.is_empty() || repo.contains('/') {
+                Err(Error::new(ErrorKind::NoRepoInPath {
+                    path: path.to_string(),
+                }))
+            } else {
+                Ok(repo)
+            }
+        }
+        None => Err(Error::new(ErrorKind::NoRepoInPath {
+            path: path.to_string(),
+        })),
+    }
+}
+
+// Note: Parse '/scm/key/repo.git' (HTTPS) or '/key/repo.git' (SSH) into 'key' and 'repo'
+pub fn bitbucket_server_slug_from_path(path: &str) -> Result<(&str, &str)> {
+    let trimmed = path.trim_start_matches('/');
+    match trimmed.strip_prefix("scm/") {
+        Some(path) => slug_from_path(path),
+        None => slug_from_path(path),
+    }
+}
+
+// Note: Parse '/~user/repo' into 'user' and 'repo'. SourceHut always prefixes owner names with '~'.
+pub fn sourcehut_slug_from_path(path: &str) -> Result<(&str, &str)> {
+    let (user, repo) = slug_from_path(path)?;
+    match user.strip_prefix('~') {
+        Some(user) if !user.is_empty() => Ok((user, repo)),
+        _ => Err(Error::new(ErrorKind::NoUserInPath {
+            path: path.to_string(),
+        })),
+    }
 }
 
 // Note: Parse '/user/repo.git' or '/user/repo' or 'user/repo' into 'user' and 'repo'.
@@ -418,6 +1823,30 @@ pub fn slug_from_path(path: &str) -> Result<(&str, &str)> {
 // Known URL formats
 //  1. https://hosting_service.com/user/repo.git
 //  2. git@hosting_service.com:user/repo.git (-> ssh://git@hosting_service.com:22/user/repo.git)
+//  3. codecommit::region://repo (-> https://git-codecommit.region.amazonaws.com/v1/repos/repo)
+// Note: Scheme and port of http:// or https:// remote URL are those of web pages unless they are
+// configured explicitly. Port of ssh:// remote URL is for SSH so it is not used.
+fn with_remote_origin(mut host_cfg: HostConfig, url: &Url) -> HostConfig {
+    if matches!(url.scheme(), "http" | "https") {
+        if host_cfg.scheme.is_none() {
+            host_cfg.scheme = Some(url.scheme().to_string());
+        }
+        if host_cfg.port.is_none() {
+            host_cfg.port = url.port();
+        }
+    }
+    host_cfg
+}
+
+// Note: Web origin is 'https://{host}' unless the remote URL is http:// or https:// with port
+fn web_origin(host: &str, url: &Url) -> String {
+    match (url.scheme(), url.port()) {
+        (scheme @ ("http" | "https"), Some(port)) => format!("{}://{}:{}", scheme, host, port),
+        (scheme @ ("http" | "https"), None) => format!("{}://{}", scheme, host),
+        _ => format!("https://{}", host),
+    }
+}
+
 pub fn build_page_url(page: &Page, cfg: &Config) -> Result<String> {
     let repo_url = &cfg.repo_url;
     let url = Url::parse(repo_url).map_err(|e| {
@@ -428,6 +1857,15 @@ pub fn build_page_url(page: &Page, cfg: &Config) -> Result<String> {
     })?;
     let env = &cfg.env;
 
+    // Note: git-remote-codecommit's 'codecommit::{region}://{repo}' is converted into HTTPS URL in
+    // argv.rs. Region is mandatory to build console URLs.
+    if url.scheme() == "codecommit" {
+        return Error::err(ErrorKind::BrokenUrl {
+            url: repo_url.to_string(),
+            msg: "Region must be specified like 'codecommit::{region}://{repo}'".to_string(),
+        });
+    }
+
     let path = url.path();
     let host = url.host_str().ok_or_else(|| {
         Error::new(ErrorKind::BrokenUrl {
@@ -436,60 +1874,129 @@ pub fn build_page_url(page: &Page, cfg: &Config) -> Result<String> {
         })
     })?;
 
-    let (user, repo_name) = if is_azure_devops_host(host) {
-        azure_devops_slug_from_path(path)?
+    if let Some(region) = codecommit_region(host) {
+        let repo_name = codecommit_repo_from_path(path)?;
+        return build_codecommit_url(region, repo_name, cfg, page);
+    }
+
+    // Note: Self-hosted services may be served with non-default scheme or port
+    let origin = web_origin(host, &url);
+
+    if let Some((gitiles, gerrit)) = gerrit_hosts(host, &origin, env) {
+        let project = gerrit_project_from_path(path)?;
+        return build_gerrit_url(&gitiles, &gerrit, project, cfg, page);
+    }
+
+    if let Some(host) = pagure_host(host, env) {
+        let origin = web_origin(host, &url);
+        let project = project_path_from_path(path)?;
+        // Note: Forks are cloned from 'forks/{user}/{repo}' via SSH but browsed at 'fork/...'
+        return match project.strip_prefix("forks/") {
+            Some(fork) => build_pagure_url(&origin, &format!("fork/{}", fork), cfg, page),
+            None => build_pagure_url(&origin, project, cfg, page),
+        };
+    }
+
+    if host == "gist.github.com" {
+        return build_gist_url(path, cfg, page);
+    }
+
+    if host == "git.launchpad.net" {
+        return build_launchpad_url(project_path_from_path(path)?, cfg, page);
+    }
+
+    if is_phabricator_host(host, env) {
+        let (kind, id) = phabricator_repo_from_path(path)?;
+        return build_phabricator_url(&origin, kind, id, cfg, page);
+    }
+
+    if env.cgit_url_host.as_deref() == Some(host) {
+        return build_cgit_url(&origin, plain_repo_from_path(path)?, cfg, page);
+    }
+
+    if env.gitweb_url_host.as_deref() == Some(host) {
+        return build_gitweb_url(&origin, plain_repo_from_path(path)?, cfg, page);
+    }
+
+    if is_azure_devops_host(host) {
+        let (org, project, repo) = azure_devops_repo_from_path(host, path)?;
+        // Note: Keep legacy visualstudio.com host for repositories cloned from it
+        let org_url = if host.ends_with(".visualstudio.com") {
+            format!("https://{}.visualstudio.com", org)
+        } else {
+            format!("https://dev.azure.com/{}", org)
+        };
+        return build_azure_devops_url(&org_url, org, project, repo, cfg, page);
+    }
+
+    // Note: Per-host settings in config file are prioritized over detecting services from host
+    let host_cfg = cfg.file.find_host_config(host);
+    let path = match &host_cfg {
+        Some(h) => h.strip_path_prefix(path),
+        None => path,
+    };
+
+    if let Some(h) = &host_cfg {
+        if let HostKind::Cgit | HostKind::GitWeb = h.kind {
+            let h = with_remote_origin(h.clone(), &url);
+            return build_plain_git_url(&h, plain_repo_from_path(path)?, cfg, page);
+        }
+    }
+
+    let (user, repo_name) = if host == "git.sr.ht" {
+        sourcehut_slug_from_path(path)?
+    } else if is_bitbucket_server_host(host, env) {
+        bitbucket_server_slug_from_path(path)?
     } else {
-        slug_from_path(path)?
+        match slug_from_path(path) {
+            Ok(slug) => slug,
+            Err(err) => {
+                return match cfg.file.find_url_templates(host) {
+                    Some(t) => {
+                        build_templated_url(host, t, project_path_from_path(path)?, cfg, page)
+                    }
+                    None => Err(err),
+                };
+            }
+        }
     };
 
+    if let Some(host_cfg) = host_cfg {
+        let host_cfg = with_remote_origin(host_cfg, &url);
+        return build_hosted_url(&host_cfg, user, repo_name, cfg, page);
+    }
+
     match host {
-        "github.com" => {
-            check_slash_in_user(user)?;
-            build_github_like_url(host, user, repo_name, Some("api.github.com"), cfg, page)
-        }
-        "gitlab.com" => build_gitlab_url(host, user, repo_name, cfg, page),
         "bitbucket.org" => build_bitbucket_url(user, repo_name, cfg, page),
-        "visualstudio.com" | "vs-ssh.visualstudio.com" | "dev.azure.com" | "ssh.dev.azure.com" => {
-            build_azure_devops_url(user, repo_name, cfg, page)
-        }
+        "git.sr.ht" => build_sourcehut_url(user, repo_name, cfg, page),
         _ => {
-            let is_gitlab = match &env.gitlab_url_host {
-                Some(h) if host == h => true,
-                _ => host.starts_with("gitlab."),
-            };
-            let port = if host.starts_with("github.") {
-                env.ghe_ssh_port
-            } else if is_gitlab {
-                env.gitlab_ssh_port
-            } else {
-                match &env.ghe_url_host {
-                    Some(v) if v == host => env.ghe_ssh_port,
-                    _ => {
-                        return Error::err(ErrorKind::UnknownHostingService {
-                            url: repo_url.to_string(),
-                        });
+            if is_bitbucket_server_host(host, env) {
+                return build_bitbucket_server_url(&origin, user, repo_name, cfg, page);
+            }
+            if let Some(host_cfg) = env.host_config(host) {
+                let host_cfg = with_remote_origin(host_cfg, &url);
+                return build_hosted_url(&host_cfg, user, repo_name, cfg, page);
+            }
+            if let Some(t) = cfg.file.find_url_templates(host) {
+                let project = project_path_from_path(path)?;
+                return build_templated_url(host, t, project, cfg, page);
+            }
+            if let Some(service) = probe::detect_service(host, &origin, env)? {
+                return match service.host_kind() {
+                    Some(kind) => {
+                        let host_cfg =
+                            with_remote_origin(env.detected_host_config(host, kind), &url);
+                        build_hosted_url(&host_cfg, user, repo_name, cfg, page)
                     }
-                }
-            };
-
-            let host = match port {
-                Some(port) => Cow::Owned(format!("{}:{}", host, port)),
-                None => Cow::Borrowed(host),
-            };
-
-            if is_gitlab {
-                build_gitlab_url(&host, user, repo_name, cfg, page)
-            } else {
-                check_slash_in_user(user)?;
-                build_github_like_url(
-                    &host,
-                    user,
-                    repo_name,
-                    Some(format!("{}/api/v3", host)),
-                    cfg,
-                    page,
-                )
+                    None => {
+                        let (project, repo) = bitbucket_server_slug_from_path(path)?;
+                        build_bitbucket_server_url(&origin, project, repo, cfg, page)
+                    }
+                };
             }
+            Error::err(ErrorKind::UnknownHostingService {
+                url: repo_url.to_string(),
+            })
         }
     }
 }
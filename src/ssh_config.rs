@@ -0,0 +1,260 @@
+use crate::config::EnvConfig;
+use std::fs;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+// Note: Well-known hosts to use SSH over HTTPS port. They are usually set as 'HostName' in
+// ~/.ssh/config but their web pages are hosted on the original hosts.
+const SSH_OVER_HTTPS_HOSTS: &[(&str, &str)] = &[
+    ("ssh.github.com", "github.com"),
+    ("altssh.gitlab.com", "gitlab.com"),
+    ("altssh.bitbucket.org", "bitbucket.org"),
+];
+
+// Note: Same as the maximum depth of nested 'Include' in OpenSSH
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+#[derive(Debug, Default)]
+struct Section {
+    // Note: Negated patterns start with '!'. Empty patterns never match (used for 'Match' blocks)
+    patterns: Vec<String>,
+    host_name: Option<String>,
+    port: Option<u16>,
+}
+
+impl Section {
+    fn new(patterns: Vec<String>) -> Section {
+        Section {
+            patterns,
+            ..Default::default()
+        }
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        let mut matched = false;
+        for pat in &self.patterns {
+            match pat.strip_prefix('!') {
+                Some(pat) if pattern_matches(pat, host) => return false,
+                Some(_) => {}
+                None => matched = matched || pattern_matches(pat, host),
+            }
+        }
+        matched
+    }
+}
+
+// Note: '*' matches any sequence of characters and '?' matches exactly one character
+fn pattern_matches(pattern: &str, s: &str) -> bool {
+    let mut chars = pattern.chars();
+    match chars.next() {
+        None => s.is_empty(),
+        Some('*') => {
+            let rest = chars.as_str();
+            s.char_indices()
+                .map(|(i, _)| i)
+                .chain(Some(s.len()))
+                .any(|i| pattern_matches(rest, &s[i..]))
+        }
+        Some(p) => {
+            let mut s_chars = s.chars();
+            match s_chars.next() {
+                Some(c) if p == '?' || p.eq_ignore_ascii_case(&c) => {
+                    pattern_matches(chars.as_str(), s_chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+// Note: Keyword and arguments can be separated by whitespaces or '='. Arguments may be quoted
+fn split_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let i = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let keyword = line[..i].to_ascii_lowercase();
+    let rest = line[i..]
+        .trim_start()
+        .strip_prefix('=')
+        .unwrap_or(&line[i..]);
+    let args = rest
+        .split_whitespace()
+        .map(|a| a.trim_matches('"').to_string())
+        .collect();
+    Some((keyword, args))
+}
+
+// Subset of ~/.ssh/config to resolve host aliases. Only 'Host', 'Match', 'Include', 'HostName' and
+// 'Port' are recognized and the other keywords are ignored.
+#[derive(Debug, Default)]
+pub struct SshConfig {
+    sections: Vec<Section>,
+}
+
+impl SshConfig {
+    fn path(env: &EnvConfig) -> Option<PathBuf> {
+        match &env.ssh_config_file {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::home_dir().map(|dir| dir.join(".ssh").join("config")),
+        }
+    }
+
+    // Note: Missing or unreadable config file is treated as empty config as well as ssh command
+    pub fn load(env: &EnvConfig) -> SshConfig {
+        let path = match Self::path(env) {
+            Some(path) => path,
+            None => return SshConfig::default(),
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content, path.parent().unwrap_or_else(|| Path::new(""))),
+            Err(_) => SshConfig::default(),
+        }
+    }
+
+    pub fn parse(content: &str, base_dir: &Path) -> SshConfig {
+        let mut config = SshConfig::default();
+        config.parse_content(content, base_dir, vec!["*".to_string()], 0);
+        config
+    }
+
+    // Note: Lines before the first 'Host' in the content belong to the enclosing block. It is 'Host *'
+    // at top level or the block containing the 'Include' line in included files.
+    fn parse_content(
+        &mut self,
+        content: &str,
+        base_dir: &Path,
+        patterns: Vec<String>,
+        depth: usize,
+    ) {
+        let mut current = patterns;
+        self.sections.push(Section::new(current.clone()));
+        for (keyword, args) in content.lines().filter_map(split_line) {
+            match keyword.as_str() {
+                "host" => {
+                    current = args;
+                    self.sections.push(Section::new(current.clone()));
+                }
+                "match" => {
+                    current = vec![];
+                    self.sections.push(Section::new(vec![]));
+                }
+                "include" if depth < MAX_INCLUDE_DEPTH => {
+                    for path in args.iter().flat_map(|a| include_paths(a, base_dir)) {
+                        if let Ok(content) = fs::read_to_string(&path) {
+                            self.parse_content(&content, base_dir, current.clone(), depth + 1);
+                        }
+                    }
+                    self.sections.push(Section::new(current.clone()));
+                }
+                "hostname" => {
+                    let section = self.sections.last_mut().unwrap();
+                    if section.host_name.is_none() {
+                        section.host_name = args.into_iter().next();
+                    }
+                }
+                "port" => {
+                    let section = self.sections.last_mut().unwrap();
+                    if section.port.is_none() {
+                        section.port = args.first().and_then(|p| p.parse().ok());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Returns the real host name and port of the host. As well as ssh command, the first obtained
+    // value is used for each keyword. When 'HostName' is not found, the host itself is returned.
+    pub fn resolve(&self, host: &str) -> (String, Option<u16>) {
+        let mut host_name = None;
+        let mut port = None;
+        for section in self.sections.iter().filter(|s| s.matches(host)) {
+            if host_name.is_none() {
+                host_name = section.host_name.as_deref();
+            }
+            if port.is_none() {
+                port = section.port;
+            }
+        }
+        let host_name = match host_name {
+            Some(name) => name.replace("%h", host).replace("%%", "%"),
+            None => host.to_string(),
+        };
+        (host_name, port)
+    }
+
+    // Resolve host alias of SSH URL into its real host name and port
+    //   Host github-work
+    //     HostName github.com
+    //   ssh://git@github-work:22/user/repo.git -> ssh://git@github.com:22/user/repo.git
+    // `implicit_port` is true when the port in the URL was not written by the user (e.g. port 22
+    // inserted for scp-like syntax). The implicit port is overridden by 'Port' in the config.
+    pub fn resolve_url(&self, url: String, implicit_port: bool) -> String {
+        let mut parsed = match Url::parse(&url) {
+            Ok(parsed) if parsed.scheme() == "ssh" => parsed,
+            _ => return url,
+        };
+        let host = match parsed.host_str() {
+            Some(host) => host.to_string(),
+            None => return url,
+        };
+        let (host_name, port) = self.resolve(&host);
+        let host_name = match SSH_OVER_HTTPS_HOSTS.iter().find(|(h, _)| *h == host_name) {
+            Some((_, canonical)) => canonical.to_string(),
+            None => host_name,
+        };
+        // Note: As well as ssh command, explicit port in the URL is prioritized over 'Port' in the
+        // config
+        let port = match parsed.port() {
+            Some(_) if !implicit_port => None,
+            _ => port,
+        };
+        if host_name == host && port.is_none() {
+            return url;
+        }
+        if parsed.set_host(Some(&host_name)).is_err() {
+            return url;
+        }
+        if let Some(port) = port {
+            let _ = parsed.set_port(Some(port));
+        }
+        parsed.into()
+    }
+}
+
+// Note: Relative paths are relative to the directory of the user config (~/.ssh). '*' and '?' in
+// the file name are expanded. Matched files are read in lexical order.
+fn include_paths(arg: &str, base_dir: &Path) -> Vec<PathBuf> {
+    let path = match arg.strip_prefix("~/") {
+        Some(rest) => match dirs::home_dir() {
+            Some(home) => home.join(rest),
+            None => return vec![],
+        },
+        None => base_dir.join(arg),
+    };
+
+    let name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) if name.contains(['*', '?']) => name.to_string(),
+        _ => return vec![path],
+    };
+    let dir = match path.parent() {
+        Some(dir) => dir,
+        None => return vec![],
+    };
+    let mut paths: Vec<_> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| pattern_matches(&name, n))
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    paths.sort();
+    paths
+}
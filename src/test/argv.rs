@@ -1,14 +1,35 @@
 use crate::argv::*;
-use crate::error::ErrorKind;
-use crate::test::helper::get_root_dir;
+use crate::config::FileConfig;
+use crate::error::{ErrorKind, Result};
+use crate::ssh_config::SshConfig;
+use crate::test::helper::{empty_env, get_root_dir};
 use std::env;
 use std::path::Path;
 
+// Note: Parse arguments without the user's environment variables, config files and global git config
+// since they change remote URLs (e.g. host aliases in ~/.ssh/config or insteadOf rules)
+fn parse_iter(argv: &[&str]) -> Result<Parsed> {
+    parse_iter_with_token(argv, None)
+}
+
+fn parse_iter_with_token(argv: &[&str], token: Option<String>) -> Result<Parsed> {
+    Parsed::parse_iter_with(argv, || {
+        let mut env = empty_env();
+        env.github_token = token;
+        Ok(Settings {
+            env,
+            file: FileConfig::default(),
+            ssh: SshConfig::default(),
+            global_git_config: false,
+        })
+    })
+}
+
 // TODO: This test only consider the repository name 'rhysd/git-brws.git'
 
 #[test]
 fn args_with_no_option() {
-    match Parsed::parse_iter(&["git-brws"]).unwrap() {
+    match parse_iter(&["git-brws"]).unwrap() {
         Parsed::OpenPage(c) => {
             assert!(
                 &[
@@ -32,7 +53,7 @@ fn args_with_no_option() {
         r => panic!("Failed to parse args with no option: {:?}", r),
     };
 
-    match Parsed::parse_iter(&["git-brws", "foo", "bar"]).unwrap() {
+    match parse_iter(&["git-brws", "foo", "bar"]).unwrap() {
         Parsed::OpenPage(c) => {
             assert_eq!(c.args.len(), 2);
         }
@@ -43,7 +64,7 @@ fn args_with_no_option() {
 #[test]
 fn multiple_options() {
     let dir = Path::new(file!()).parent().unwrap();
-    match Parsed::parse_iter(&[
+    match parse_iter(&[
         "git-brws",
         "-u",
         "-r",
@@ -115,7 +136,7 @@ fn fix_ssh_repo_url() {
             "ssh://git@github.somewhere.com:123/456/repo.git",
         ),
     ] {
-        match Parsed::parse_iter(&["git-brws", "-r", url]).unwrap() {
+        match parse_iter(&["git-brws", "-r", url]).unwrap() {
             Parsed::OpenPage(c) => {
                 assert_eq!(c.repo_url, *expected);
             }
@@ -124,9 +145,40 @@ fn fix_ssh_repo_url() {
     }
 }
 
+#[test]
+fn ssh_config_port_for_scp_like_repo_url() {
+    let settings = || {
+        Ok(Settings {
+            env: empty_env(),
+            file: FileConfig::default(),
+            ssh: SshConfig::parse(
+                "Host git.example.com\n    Port 2222\n",
+                Path::new("/path/to/unknown"),
+            ),
+            global_git_config: false,
+        })
+    };
+    for (url, expected) in &[
+        (
+            "git@git.example.com:user/repo.git",
+            "ssh://git@git.example.com:2222/user/repo.git",
+        ),
+        // Port number written in the URL is prioritized
+        (
+            "git@git.example.com:123:user/repo.git",
+            "ssh://git@git.example.com:123/user/repo.git",
+        ),
+    ] {
+        match Parsed::parse_iter_with(&["git-brws", "-r", url], settings).unwrap() {
+            Parsed::OpenPage(c) => assert_eq!(c.repo_url, *expected),
+            p => panic!("{:?}", p),
+        }
+    }
+}
+
 #[test]
 fn repo_formatting() {
-    let p = |r| Parsed::parse_iter(&["git-brws", "-r", r]).unwrap();
+    let p = |r| parse_iter(&["git-brws", "-r", r]).unwrap();
     match p("bitbucket.org/foo/bar") {
         Parsed::OpenPage(c) => assert_eq!(c.repo_url, "https://bitbucket.org/foo/bar.git"),
         p => panic!("{:?}", p),
@@ -141,9 +193,33 @@ fn repo_formatting() {
     }
 }
 
+#[test]
+fn codecommit_repo_url() {
+    for (url, expected) in &[
+        (
+            "codecommit::us-east-1://repo",
+            "https://git-codecommit.us-east-1.amazonaws.com/v1/repos/repo.git",
+        ),
+        (
+            "codecommit::ap-northeast-1://profile@repo",
+            "https://git-codecommit.ap-northeast-1.amazonaws.com/v1/repos/repo.git",
+        ),
+        (
+            "https://git-codecommit.us-east-1.amazonaws.com/v1/repos/repo",
+            "https://git-codecommit.us-east-1.amazonaws.com/v1/repos/repo.git",
+        ),
+        ("codecommit://repo", "codecommit://repo.git"),
+    ] {
+        match parse_iter(&["git-brws", "-r", url]).unwrap() {
+            Parsed::OpenPage(c) => assert_eq!(c.repo_url, *expected),
+            p => panic!("{:?}", p),
+        }
+    }
+}
+
 #[test]
 fn valid_remote_name() {
-    match Parsed::parse_iter(&["git-brws", "-R", "origin"]).unwrap() {
+    match parse_iter(&["git-brws", "-R", "origin"]).unwrap() {
         Parsed::OpenPage(c) => assert!(
             [
                 "https://github.com/rhysd/git-brws.git",
@@ -161,7 +237,7 @@ fn valid_remote_name() {
 
 #[test]
 fn invalid_remote_name() {
-    match Parsed::parse_iter(&["git-brws", "-R", "this-remote-is-never-existing"])
+    match parse_iter(&["git-brws", "-R", "this-remote-is-never-existing"])
         .unwrap_err()
         .kind()
     {
@@ -175,7 +251,7 @@ fn invalid_remote_name() {
 
 #[test]
 fn help_option() {
-    match Parsed::parse_iter(&["git-brws", "-h"]).unwrap() {
+    match parse_iter(&["git-brws", "-h"]).unwrap() {
         Parsed::Help(s) => {
             assert!(s.starts_with("Usage:"));
         }
@@ -185,7 +261,7 @@ fn help_option() {
 
 #[test]
 fn version_option() {
-    match Parsed::parse_iter(&["git-brws", "-v"]).unwrap() {
+    match parse_iter(&["git-brws", "-v"]).unwrap() {
         Parsed::Version(s) => {
             assert!(!s.is_empty());
         }
@@ -195,15 +271,14 @@ fn version_option() {
 
 #[test]
 fn unknown_options() {
-    assert!(Parsed::parse_iter(&["git-brws", "--unknown"]).is_err());
+    assert!(parse_iter(&["git-brws", "--unknown"]).is_err());
 }
 
 // For checking #9
 #[test]
 fn specify_repo_outside_repository() {
     let root = get_root_dir();
-    match Parsed::parse_iter(&["git-brws", "-d", root.to_str().unwrap(), "-r", "foo/bar"]).unwrap()
-    {
+    match parse_iter(&["git-brws", "-d", root.to_str().unwrap(), "-r", "foo/bar"]).unwrap() {
         Parsed::OpenPage(c) => {
             assert_eq!(c.cwd, root);
             assert_eq!(&c.repo_url, "https://github.com/foo/bar.git");
@@ -214,11 +289,11 @@ fn specify_repo_outside_repository() {
 
 #[test]
 fn search_repo_from_github_by_name() {
-    skip_if_no_token_for_search!();
+    let token = skip_if_no_token_for_search!();
 
     // Add user:rhysd to ensure to get expected result. But actually repository name is usually
     // passed like `-r react` as use case.
-    let parsed = Parsed::parse_iter(&["git-brws", "-r", "user:rhysd vim.wasm"]).unwrap();
+    let parsed = parse_iter_with_token(&["git-brws", "-r", "user:rhysd vim.wasm"], token).unwrap();
     match parsed {
         Parsed::OpenPage(c) => {
             assert_eq!(&c.repo_url, "https://github.com/rhysd/vim.wasm.git");
@@ -229,7 +304,7 @@ fn search_repo_from_github_by_name() {
 
 #[test]
 fn repo_specified_but_argument_is_not_empty() {
-    let err = Parsed::parse_iter(&["git-brws", "-r", "foo", "HEAD"]).unwrap_err();
+    let err = parse_iter(&["git-brws", "-r", "foo", "HEAD"]).unwrap_err();
     match err.kind() {
         ErrorKind::ArgsNotAllowed { args, .. } => {
             assert!(format!("{}", err).contains("\"HEAD\""), "{:?}", args);
@@ -240,7 +315,7 @@ fn repo_specified_but_argument_is_not_empty() {
 
 #[test]
 fn current_branch_flag() {
-    match Parsed::parse_iter(&["git-brws", "-c"]).unwrap() {
+    match parse_iter(&["git-brws", "-c"]).unwrap() {
         Parsed::OpenPage(c) => {
             assert!(c.branch.is_some());
         }
@@ -249,7 +324,7 @@ fn current_branch_flag() {
 
     // When --current-branch and --branch are both specified, --branch is prioritized.
     // Note: -r is necessary to avoid "no such branch 'dev'" error.
-    match Parsed::parse_iter(&["git-brws", "-r", "foo/bar", "-c", "-b", "dev"]).unwrap() {
+    match parse_iter(&["git-brws", "-r", "foo/bar", "-c", "-b", "dev"]).unwrap() {
         Parsed::OpenPage(c) => {
             assert_eq!(c.branch, Some("dev".to_string()));
         }
@@ -260,7 +335,7 @@ fn current_branch_flag() {
 // #26
 #[test]
 fn branch_name_is_empty() {
-    let e = Parsed::parse_iter(&["git-brws", "-b", ""]).unwrap_err();
+    let e = parse_iter(&["git-brws", "-b", ""]).unwrap_err();
     let k = e.kind();
     assert!(matches!(k, ErrorKind::BranchNameEmpty), "{:?}", k);
 }
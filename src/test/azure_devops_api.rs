@@ -0,0 +1,50 @@
+use crate::azure_devops_api::{parse_default_branch, parse_pr_id, Client};
+use crate::error::ErrorKind;
+
+#[tokio::test]
+async fn request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/org/project",
+        &None::<&str>,
+        &None::<&str>,
+    )
+    .unwrap();
+    match client.default_branch("repo").await.unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn find_pr_request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/org/project",
+        &Some("dummy token"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client
+        .find_pr_id("branch", "repo")
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn parse_pr_id_response() {
+    let body = include_str!("testdata/azure_devops_pullrequests.json");
+    assert_eq!(parse_pr_id(body).unwrap(), Some(42));
+    assert_eq!(parse_pr_id(r#"{"value":[],"count":0}"#).unwrap(), None);
+}
+
+#[test]
+fn parse_default_branch_strips_refs_heads() {
+    let body = include_str!("testdata/azure_devops_repository.json");
+    assert_eq!(parse_default_branch(body).unwrap().as_deref(), Some("main"));
+    // Empty repository has no default branch
+    assert_eq!(parse_default_branch(r#"{"name":"repo"}"#).unwrap(), None);
+}
@@ -0,0 +1,45 @@
+use crate::bitbucket_api::{parse_pr_url, Client};
+use crate::error::ErrorKind;
+
+#[tokio::test]
+async fn request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/2.0",
+        &Some("user:app-password"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client.repo("user", "repo").await.unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn find_pr_request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/2.0",
+        &Some("access-token"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client
+        .find_pr_url("branch", "user", "repo", Some("fork/repo"))
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn parse_pr_url_from_html_link() {
+    let body = include_str!("testdata/bitbucket_pullrequests.json");
+    assert_eq!(
+        parse_pr_url(body).unwrap().as_deref(),
+        Some("https://bitbucket.org/owner/repo/pull-requests/3"),
+    );
+    assert_eq!(parse_pr_url(r#"{"values":[]}"#).unwrap(), None);
+}
@@ -0,0 +1,31 @@
+use crate::bitbucket_server_api::{parse_pr_url, Client};
+use crate::error::ErrorKind;
+
+#[tokio::test]
+async fn request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/rest/api/1.0",
+        &Some("dummy token"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client
+        .find_pr_url("branch", "PROJ", "repo")
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn parse_pr_url_from_self_link() {
+    let body = include_str!("testdata/bitbucket_server_pull_requests.json");
+    assert_eq!(
+        parse_pr_url(body).unwrap().as_deref(),
+        Some("https://bitbucket.example.com/projects/PROJ/repos/repo/pull-requests/5"),
+    );
+    assert_eq!(parse_pr_url(r#"{"values":[]}"#).unwrap(), None);
+}
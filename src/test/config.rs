@@ -1,15 +1,18 @@
-use crate::config::EnvConfig;
+use crate::config::{EnvConfig, FileConfig, HostConfig, HostKind};
 use crate::error::ErrorKind;
 use crate::test::helper::empty_env;
 use std::env;
 
 #[test]
 fn invalid_env_value() {
-    let vars = vec![("GIT_BRWS_GHE_SSH_PORT".to_string(), "hello".to_string())];
-    match EnvConfig::from_iter(vars.into_iter()).unwrap_err().kind() {
+    let vars = vec![(
+        "GIT_BRWS_SHORT_COMMIT_HASH".to_string(),
+        "hello".to_string(),
+    )];
+    match EnvConfig::from_iter(vars).unwrap_err().kind() {
         ErrorKind::EnvLoadError(e) => {
             let msg = format!("{}", e);
-            assert!(msg.contains("invalid digit found in string"), "{}", msg);
+            assert!(msg.contains("hello"), "{}", msg);
         }
         err => panic!("Unexpected error: {}", err),
     }
@@ -27,7 +30,6 @@ fn with_global_env() {
     let env = EnvConfig::from_iter(iter).unwrap().with_global_env();
 
     assert_eq!(env.git_command, "git");
-    assert_eq!(env.gitlab_ssh_port, None);
     assert_eq!(env.ghe_token.unwrap(), "token for ghe");
     assert!(env.short_commit_hash);
 
@@ -54,3 +56,202 @@ fn with_global_env_updates_none_values() {
     assert_eq!(env.https_proxy, https_proxy);
     assert_eq!(env.github_token, github_token);
 }
+
+#[test]
+fn parse_config_file() {
+    let file = FileConfig::parse(
+        r#"
+[[services]]
+host = "git.*.example.com"
+repo = "https://{host}/{user}/{repo}"
+
+[[services]]
+host = "code.example.com"
+commit = "https://{host}/{repo}/commit/{hash}"
+"#,
+    )
+    .unwrap();
+    assert_eq!(file.services.len(), 2);
+
+    let t = file.find_url_templates("git.foo.example.com").unwrap();
+    assert_eq!(t.repo.as_deref(), Some("https://{host}/{user}/{repo}"));
+    assert_eq!(t.commit, None);
+    let t = file.find_url_templates("code.example.com").unwrap();
+    assert_eq!(t.host, "code.example.com");
+    for host in &["git.example.com", "example.com", "code.example.com.evil"] {
+        assert!(file.find_url_templates(host).is_none(), "{}", host);
+    }
+}
+
+#[test]
+fn parse_broken_config_file() {
+    for content in &[
+        "[[services]]\nhost = \"example.com\"\nunknown = \"https://{host}\"",
+        "[[services]]\nrepo = \"https://{host}\"",
+        "services = 42",
+    ] {
+        assert!(FileConfig::parse(content).is_err(), "{}", content);
+    }
+}
+
+#[test]
+fn load_config_file() {
+    let mut env = empty_env();
+    env.config_file = Some("/path/to/unknown/config.toml".to_string());
+    match FileConfig::load(&env).unwrap_err().kind() {
+        ErrorKind::ConfigFileError { .. } => { /* ok */ }
+        err => panic!("Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn parse_host_configs() {
+    let file = FileConfig::parse(
+        r#"
+[[hosts]]
+host = "ghe1.example.com"
+kind = "github"
+token = "token1"
+
+[[hosts]]
+host = "*.gitlab.example.com"
+kind = "gitlab"
+scheme = "http"
+port = 8080
+api_base = "https://api.gitlab.example.com/v4/"
+"#,
+    )
+    .unwrap();
+
+    let h = file.find_host_config("ghe1.example.com").unwrap();
+    assert_eq!(h.kind, HostKind::GitHub);
+    assert_eq!(h.scheme(), "https");
+    assert_eq!(h.web_host(), "ghe1.example.com");
+    assert_eq!(h.api_endpoint(), "ghe1.example.com/api/v3");
+    assert_eq!(h.token.as_deref(), Some("token1"));
+
+    let h = file.find_host_config("foo.gitlab.example.com").unwrap();
+    assert_eq!(h.kind, HostKind::GitLab);
+    assert_eq!(h.host, "foo.gitlab.example.com");
+    assert_eq!(h.scheme(), "http");
+    assert_eq!(h.web_host(), "foo.gitlab.example.com:8080");
+    assert_eq!(h.api_endpoint(), "api.gitlab.example.com/v4");
+
+    assert!(file.find_host_config("ghe2.example.com").is_none());
+    assert!(FileConfig::parse("[[hosts]]\nhost = \"example.com\"\nkind = \"gogs\"").is_err());
+
+    let file = FileConfig::parse(
+        "[[hosts]]\nhost = \"a.example.com\"\nkind = \"cgit\"\n\n[[hosts]]\nhost = \"b.example.com\"\nkind = \"gitweb\"",
+    )
+    .unwrap();
+    assert_eq!(
+        file.find_host_config("a.example.com").unwrap().kind,
+        HostKind::Cgit
+    );
+    assert_eq!(
+        file.find_host_config("b.example.com").unwrap().kind,
+        HostKind::GitWeb
+    );
+}
+
+#[test]
+fn host_config_with_deprecated_ssh_port() {
+    let file = FileConfig::parse(
+        r#"
+[[hosts]]
+host = "ghe.example.com"
+kind = "github"
+ssh_port = 10022
+"#,
+    )
+    .unwrap();
+
+    let h = file.find_host_config("ghe.example.com").unwrap();
+    assert_eq!(h.web_host(), "ghe.example.com");
+    assert_eq!(h.api_endpoint(), "ghe.example.com/api/v3");
+}
+
+#[test]
+fn host_config_path_prefix() {
+    let file = FileConfig::parse(
+        r#"
+[[hosts]]
+host = "example.com"
+kind = "gitea"
+path_prefix = "/git/"
+"#,
+    )
+    .unwrap();
+
+    let h = file.find_host_config("example.com").unwrap();
+    assert_eq!(h.kind, HostKind::Gitea);
+    assert_eq!(h.path_prefix(), Some("git"));
+    assert_eq!(h.web_base(), "example.com/git");
+    assert_eq!(h.api_endpoint(), "example.com/git/api/v1");
+    assert_eq!(h.strip_path_prefix("/git/user/repo.git"), "/user/repo.git");
+    assert_eq!(h.strip_path_prefix("/user/repo.git"), "/user/repo.git");
+    assert_eq!(h.strip_path_prefix("/github/repo.git"), "/github/repo.git");
+}
+
+#[test]
+fn host_config_from_env() {
+    let mut env = empty_env();
+    env.ghe_url_host = Some("ghe.example.com".to_string());
+    env.ghe_token = Some("ghe token".to_string());
+    env.github_token = Some("github token".to_string());
+
+    let h = env.host_config("github.com").unwrap();
+    assert_eq!(h.kind, HostKind::GitHub);
+    assert_eq!(h.api_endpoint(), "api.github.com");
+    assert_eq!(h.token.as_deref(), Some("github token"));
+
+    let h = env.host_config("ghe.example.com").unwrap();
+    assert_eq!(h.kind, HostKind::GitHub);
+    assert_eq!(h.web_host(), "ghe.example.com");
+    assert_eq!(h.api_endpoint(), "ghe.example.com/api/v3");
+    assert_eq!(h.token.as_deref(), Some("ghe token"));
+
+    let h = env.host_config("gitlab.example.com").unwrap();
+    assert_eq!(h.kind, HostKind::GitLab);
+    assert_eq!(h.token, None);
+
+    let h = env.host_config("gitea.example.com").unwrap();
+    assert_eq!(h.kind, HostKind::Gitea);
+    assert_eq!(h.api_endpoint(), "gitea.example.com/api/v1");
+
+    let h = HostConfig {
+        scheme: Some("http".to_string()),
+        port: Some(8080),
+        ..env.host_config("gitlab.example.com").unwrap()
+    };
+    assert_eq!(h.api_endpoint(), "http://gitlab.example.com:8080/api/v4");
+
+    let h = env.host_config("octocorp.ghe.com").unwrap();
+    assert_eq!(h.kind, HostKind::GitHub);
+    assert_eq!(h.web_host(), "octocorp.ghe.com");
+    assert_eq!(h.api_endpoint(), "api.octocorp.ghe.com");
+    assert_eq!(h.token.as_deref(), Some("ghe token"));
+
+    assert!(env.host_config("example.com").is_none());
+}
+
+#[test]
+fn use_token_embedded_in_remote_url() {
+    let mut env = empty_env();
+    env.gitlab_token = Some("gitlab token".to_string());
+    env.bitbucket_server_url_host = Some("bbs.example.com".to_string());
+
+    env.use_embedded_token("github.com", "github token".to_string());
+    env.use_embedded_token("github.example.com", "ghe token".to_string());
+    env.use_embedded_token("gitlab.com", "embedded gitlab token".to_string());
+    env.use_embedded_token("bbs.example.com", "bbs token".to_string());
+    env.use_embedded_token("example.com", "unknown token".to_string());
+
+    assert_eq!(env.github_token.as_deref(), Some("github token"));
+    assert_eq!(env.ghe_token.as_deref(), Some("ghe token"));
+    // Configured token is prioritized
+    assert_eq!(env.gitlab_token.as_deref(), Some("gitlab token"));
+    assert_eq!(env.bitbucket_server_token.as_deref(), Some("bbs token"));
+    assert_eq!(env.gitea_token, None);
+    assert_eq!(env.bitbucket_token, None);
+}
@@ -0,0 +1,51 @@
+use crate::error::ErrorKind;
+use crate::gerrit_api::{parse_change_number, Client};
+
+#[tokio::test]
+async fn request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com",
+        &Some("user:password"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client
+        .find_change_number("I0123456789abcdef0123456789abcdef01234567", "project")
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn invalid_token() {
+    match Client::build(
+        "unknown.endpoint.example.com",
+        &Some("password-without-user"),
+        &None::<&str>,
+    ) {
+        Err(err) => match err.kind() {
+            ErrorKind::InvalidToken { .. } => { /* ok */ }
+            e => panic!("unexpected error: {}", e),
+        },
+        Ok(_) => panic!("token without user name must be rejected"),
+    }
+}
+
+#[test]
+fn parse_change_number_with_magic_prefix() {
+    let body = include_str!("testdata/gerrit_changes.json");
+    assert_eq!(parse_change_number(body).unwrap(), Some(3965));
+    assert_eq!(parse_change_number(")]}'\n[]").unwrap(), None);
+}
+
+#[test]
+fn parse_change_number_without_magic_prefix() {
+    assert_eq!(
+        parse_change_number(r#"[{"_number":12}]"#).unwrap(),
+        Some(12)
+    );
+}
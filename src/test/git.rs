@@ -1,6 +1,8 @@
 use crate::error::ErrorKind;
-use crate::git::Git;
+use crate::git::{local_repo_path, rewrite_url, Git};
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 #[test]
 fn git_get_current_branch() {
@@ -136,3 +138,150 @@ fn git_root_directory_not_found() {
     let err = g.root_dir().unwrap_err();
     assert!(matches!(err.kind(), ErrorKind::GitRootDirNotFound { .. }));
 }
+
+#[test]
+fn rewrite_url_with_rules() {
+    let rules = vec![
+        ("git@github.com:".to_string(), "gh:".to_string()),
+        (
+            "https://github.com/".to_string(),
+            "https://mirror.example.com/".to_string(),
+        ),
+        (
+            "https://gitlab.com/".to_string(),
+            "https://mirror.example.com/gitlab/".to_string(),
+        ),
+    ];
+    assert_eq!(
+        rewrite_url("gh:user/repo", &rules).as_deref(),
+        Some("git@github.com:user/repo"),
+    );
+    // The longest matched prefix wins
+    assert_eq!(
+        rewrite_url("https://mirror.example.com/gitlab/user/repo.git", &rules).as_deref(),
+        Some("https://gitlab.com/user/repo.git"),
+    );
+    assert_eq!(
+        rewrite_url("https://mirror.example.com/user/repo.git", &rules).as_deref(),
+        Some("https://github.com/user/repo.git"),
+    );
+    assert_eq!(rewrite_url("git@github.com:user/repo.git", &rules), None);
+    assert_eq!(rewrite_url("gh:user/repo", &[]), None);
+}
+
+#[test]
+fn git_remote_url_with_rewrite_rules() {
+    let dir = env::temp_dir().join("git-brws-test-url-rewrite");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let g = Git::new(&dir, "git");
+    for args in &[
+        &["init", "--quiet"][..],
+        &["remote", "add", "origin", "gh:user/repo.git"],
+        &["remote", "add", "push", "ghp:user/repo.git"],
+        &[
+            "remote",
+            "add",
+            "mirror",
+            "https://mirror.example.com/user/repo.git",
+        ],
+        &["config", "url.git@github.com:.insteadOf", "gh:"],
+        &["config", "url.ssh://git@github.com/.pushInsteadOf", "ghp:"],
+        &[
+            "config",
+            "url.https://github.com/.brwsInsteadOf",
+            "https://mirror.example.com/",
+        ],
+    ] {
+        g.command(args).unwrap();
+    }
+
+    assert_eq!(
+        g.remote_url("origin").unwrap(),
+        "git@github.com:user/repo.git"
+    );
+    // Note: pushInsteadOf only affects push URLs as well as git
+    assert_eq!(g.remote_url("push").unwrap(), "ghp:user/repo.git");
+    assert_eq!(
+        g.remote_url("mirror").unwrap(),
+        "https://github.com/user/repo.git"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn local_repo_path_of_remote_url() {
+    let base = Path::new("/path/to/repo");
+    for (url, expected) in &[
+        ("/srv/mirrors/repo.git", Some("/srv/mirrors/repo.git")),
+        (
+            "file:///srv/mirrors/repo.git",
+            Some("/srv/mirrors/repo.git"),
+        ),
+        ("../repo.git", Some("/path/to/repo/../repo.git")),
+        ("./mirror", Some("/path/to/repo/./mirror")),
+        ("https://github.com/user/repo.git", None),
+        ("ssh://git@github.com:22/user/repo.git", None),
+        ("git@github.com:user/repo.git", None),
+        ("host:repo", None),
+        ("repo.git", None),
+    ] {
+        assert_eq!(
+            local_repo_path(url, base),
+            expected.map(PathBuf::from),
+            "{}",
+            url
+        );
+    }
+}
+
+fn init_repo(dir: &Path, origin: &str) {
+    fs::create_dir_all(dir).unwrap();
+    let g = Git::new(dir, "git");
+    g.command(&["init", "--quiet", "--bare"]).unwrap();
+    g.command(&["remote", "add", "origin", origin]).unwrap();
+}
+
+#[test]
+fn follow_local_remote_recursively() {
+    let dir = env::temp_dir().join("git-brws-test-follow-local-remote");
+    let _ = fs::remove_dir_all(&dir);
+    let upstream = "https://github.com/user/repo.git";
+    init_repo(&dir.join("mirror2.git"), upstream);
+    let mirror2 = format!("file://{}", dir.join("mirror2.git").to_str().unwrap());
+    init_repo(&dir.join("mirror1.git"), &mirror2);
+    init_repo(&dir.join("repo"), "../mirror1.git");
+
+    let repo = dir.join("repo");
+    let g = Git::new(&repo, "git");
+    let url = g.remote_url("origin").unwrap();
+    assert_eq!(g.follow_local_remote(url).unwrap(), upstream);
+    assert_eq!(
+        g.follow_local_remote(upstream.to_string()).unwrap(),
+        upstream
+    );
+
+    // Not existing path is not followed
+    let url = "/path/to/unknown/repo.git".to_string();
+    assert_eq!(g.follow_local_remote(url.clone()).unwrap(), url);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn follow_local_remote_loop() {
+    let dir = env::temp_dir().join("git-brws-test-follow-local-remote-loop");
+    let _ = fs::remove_dir_all(&dir);
+    init_repo(&dir.join("a.git"), "../b.git");
+    init_repo(&dir.join("b.git"), "../a.git");
+
+    let g = Git::new(&dir, "git");
+    let url = dir.join("a.git").to_str().unwrap().to_string();
+    match g.follow_local_remote(url).unwrap_err().kind() {
+        ErrorKind::LocalRemoteLoop { paths } => assert_eq!(paths.len(), 3, "{:?}", paths),
+        e => panic!("Unexpected error: {:?}", e),
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,79 @@
+use crate::error::ErrorKind;
+use crate::gitea_api::{parse_pr_url, Client};
+
+#[tokio::test]
+async fn request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/api/v1",
+        &None::<&str>,
+        &None::<&str>,
+    )
+    .unwrap();
+    match client.repo("user", "repo").await.unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn find_pr_request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/api/v1",
+        &Some("dummy token"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client
+        .find_pr_url("branch", "user", "repo", None)
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+const PULLS: &str = include_str!("testdata/gitea_pulls.json");
+
+#[test]
+fn parse_pr_url_by_branch() {
+    assert_eq!(
+        parse_pr_url(PULLS, "feature", None).unwrap().as_deref(),
+        Some("https://gitea.com/owner/repo/pulls/12"),
+    );
+    assert_eq!(parse_pr_url(PULLS, "unknown", None).unwrap(), None);
+}
+
+#[test]
+fn parse_pr_url_by_author() {
+    assert_eq!(
+        parse_pr_url(PULLS, "feature", Some("author"))
+            .unwrap()
+            .as_deref(),
+        Some("https://gitea.com/owner/repo/pulls/11"),
+    );
+    assert_eq!(
+        parse_pr_url(PULLS, "feature", Some("someone")).unwrap(),
+        None
+    );
+    // Pull request from deleted fork does not match any author
+    assert_eq!(
+        parse_pr_url(PULLS, "deleted-fork", Some("author")).unwrap(),
+        None,
+    );
+    assert_eq!(
+        parse_pr_url(PULLS, "deleted-fork", None)
+            .unwrap()
+            .as_deref(),
+        Some("https://gitea.com/owner/repo/pulls/10"),
+    );
+}
+
+#[test]
+fn parse_broken_response() {
+    match parse_pr_url("{}", "feature", None).unwrap_err().kind() {
+        ErrorKind::ApiResponseParseError { service, .. } => assert_eq!(*service, "Gitea"),
+        e => panic!("unexpected error: {}", e),
+    }
+}
@@ -132,3 +132,18 @@ async fn homepage_error_response() {
         .await
         .unwrap_err();
 }
+
+#[tokio::test]
+async fn gist_owner_request_failure() {
+    let client =
+        Client::build("unknown.endpoint.example.com", &None::<&str>, &None::<&str>).unwrap();
+    match client
+        .gist_owner("0123456789abcdef")
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
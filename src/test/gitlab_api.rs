@@ -0,0 +1,86 @@
+use crate::error::ErrorKind;
+use crate::gitlab_api::{parse_mr_url, parse_version, Client};
+
+#[tokio::test]
+async fn request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/api/v4",
+        &None::<&str>,
+        &None::<&str>,
+    )
+    .unwrap();
+    match client.project("group/repo").await.unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn find_mr_request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/api/v4",
+        &Some("dummy token"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client
+        .find_mr_url("branch", "group/subgroup/repo", Some(42))
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn version_request_failure() {
+    let client = Client::build(
+        "unknown.endpoint.example.com/api/v4",
+        &Some("dummy token"),
+        &None::<&str>,
+    )
+    .unwrap();
+    match client.version().await.unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+const MERGE_REQUESTS: &str = include_str!("testdata/gitlab_merge_requests.json");
+
+#[test]
+fn parse_mr_url_from_any_project() {
+    assert_eq!(
+        parse_mr_url(MERGE_REQUESTS, None).unwrap().as_deref(),
+        Some("https://gitlab.com/group/repo/-/merge_requests/8"),
+    );
+    assert_eq!(parse_mr_url("[]", None).unwrap(), None);
+}
+
+#[test]
+fn parse_mr_url_from_source_project() {
+    assert_eq!(
+        parse_mr_url(MERGE_REQUESTS, Some(200)).unwrap().as_deref(),
+        Some("https://gitlab.com/group/repo/-/merge_requests/7"),
+    );
+    assert_eq!(parse_mr_url(MERGE_REQUESTS, Some(300)).unwrap(), None);
+}
+
+#[test]
+fn parse_version_response() {
+    let body = include_str!("testdata/gitlab_version.json");
+    assert_eq!(parse_version(body).unwrap(), "16.5.1-ee");
+}
+
+#[test]
+fn parse_broken_response() {
+    match parse_mr_url(r#"{"message":"404 Not found"}"#, None)
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::ApiResponseParseError { service, .. } => assert_eq!(*service, "GitLab"),
+        e => panic!("unexpected error: {}", e),
+    }
+}
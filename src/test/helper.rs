@@ -6,14 +6,36 @@ use std::path::PathBuf;
 pub fn empty_env() -> EnvConfig {
     EnvConfig {
         git_command: "git".to_string(),
-        ghe_ssh_port: None,
         ghe_url_host: None,
         gitlab_url_host: None,
-        gitlab_ssh_port: None,
+        gitea_url_host: None,
+        bitbucket_server_url_host: None,
+        gerrit_url_host: None,
+        cgit_url_host: None,
+        gitweb_url_host: None,
+        phabricator_url_host: None,
+        pagure_url_host: None,
         github_token: None,
         ghe_token: None,
+        gitlab_token: None,
+        gitea_token: None,
+        bitbucket_token: None,
+        bitbucket_server_token: None,
+        azure_devops_token: None,
+        gerrit_token: None,
+        pagure_token: None,
         https_proxy: None,
         browse_command: None,
+        config_file: None,
+        // Note: Tests must not send requests to unknown hosts nor touch the user's cache file
+        cache_file: Some(
+            env::temp_dir()
+                .join("git-brws-test-hosts.json")
+                .to_string_lossy()
+                .into_owned(),
+        ),
+        probe_unknown_hosts: false,
+        ssh_config_file: None,
         short_commit_hash: false,
     }
 }
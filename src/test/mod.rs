@@ -2,11 +2,21 @@
 mod helper;
 
 mod argv;
+mod azure_devops_api;
+mod bitbucket_api;
+mod bitbucket_server_api;
 mod config;
+mod credential;
 mod error;
+mod gerrit_api;
 mod git;
+mod gitea_api;
 mod github_api;
+mod gitlab_api;
 mod page;
+mod pagure_api;
+mod probe;
 mod pull_request;
 mod service;
+mod ssh_config;
 mod url;
@@ -1,4 +1,4 @@
-use crate::config::Config;
+use crate::config::{Config, FileConfig};
 use crate::error::ErrorKind;
 use crate::page::{parse_page, DiffOp, Line, Page};
 use crate::test::helper::{empty_env, get_root_dir};
@@ -17,6 +17,7 @@ fn config(repo: &str, branch: Option<&str>, args: Vec<&str>) -> Config {
         blame: false,
         remote: None,
         env: empty_env(),
+        file: FileConfig::default(),
     }
 }
 
@@ -0,0 +1,25 @@
+use crate::error::ErrorKind;
+use crate::pagure_api::{parse_pr_id, Client};
+
+#[tokio::test]
+async fn request_failure() {
+    let client =
+        Client::build("unknown.endpoint.example.com", &None::<&str>, &None::<&str>).unwrap();
+    match client
+        .find_pr_id("branch", "rpms/bash")
+        .await
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn parse_pr_id_by_branch_from() {
+    let body = include_str!("testdata/pagure_pull_requests.json");
+    assert_eq!(parse_pr_id(body, "feature").unwrap(), Some(20));
+    assert_eq!(parse_pr_id(body, "other").unwrap(), Some(21));
+    assert_eq!(parse_pr_id(body, "main").unwrap(), None);
+}
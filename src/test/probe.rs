@@ -0,0 +1,104 @@
+use crate::probe::{detect_service, probe_service, Cache, Service};
+use crate::test::helper::empty_env;
+use std::env;
+use std::fs;
+
+#[test]
+fn save_and_load_cache() {
+    let path = env::temp_dir()
+        .join("git-brws-test-save-and-load-cache")
+        .join("hosts.json");
+    let mut env = empty_env();
+    env.cache_file = Some(path.to_str().unwrap().to_string());
+
+    let mut cache = Cache::default();
+    cache
+        .hosts
+        .insert("code.example.com".to_string(), Service::GitLab);
+    cache
+        .hosts
+        .insert("scm.example.com".to_string(), Service::BitbucketServer);
+    cache.save(&env).unwrap();
+
+    assert_eq!(Cache::load(&env), cache);
+    assert_eq!(
+        detect_service("scm.example.com", "https://scm.example.com", &env).unwrap(),
+        None,
+    );
+    env.probe_unknown_hosts = true;
+    assert_eq!(
+        detect_service("scm.example.com", "https://scm.example.com", &env).unwrap(),
+        Some(Service::BitbucketServer),
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cache_unknown_hosts() {
+    let path = env::temp_dir()
+        .join("git-brws-test-cache-unknown-hosts")
+        .join("hosts.json");
+    let mut env = empty_env();
+    env.cache_file = Some(path.to_str().unwrap().to_string());
+    env.probe_unknown_hosts = true;
+
+    let mut cache = Cache::default();
+    cache
+        .unknown_hosts
+        .insert("unknown.endpoint.example.com".to_string(), 1_000_000);
+    assert!(cache.is_recently_unknown("unknown.endpoint.example.com", 1_000_000));
+    assert!(cache.is_recently_unknown("unknown.endpoint.example.com", 1_000_000 + 60 * 60));
+    assert!(!cache.is_recently_unknown("unknown.endpoint.example.com", 1_000_000 + 24 * 60 * 60));
+    assert!(!cache.is_recently_unknown("other.example.com", 1_000_000));
+
+    // Host where no service is detected is cached with the time of probing
+    assert_eq!(
+        detect_service(
+            "unknown.endpoint.example.com",
+            "https://unknown.endpoint.example.com",
+            &env,
+        )
+        .unwrap(),
+        None,
+    );
+    let cache = Cache::load(&env);
+    assert!(cache.hosts.is_empty());
+    let probed = cache.unknown_hosts["unknown.endpoint.example.com"];
+    assert!(cache.is_recently_unknown("unknown.endpoint.example.com", probed));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_cache_without_unknown_hosts() {
+    let path = env::temp_dir().join("git-brws-test-cache-without-unknown-hosts.json");
+    fs::write(&path, "{\"hosts\": {\"example.com\": \"gitlab\"}}").unwrap();
+    let mut env = empty_env();
+    env.cache_file = Some(path.to_str().unwrap().to_string());
+    let cache = Cache::load(&env);
+    assert_eq!(cache.hosts.get("example.com"), Some(&Service::GitLab));
+    assert!(cache.unknown_hosts.is_empty());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_broken_cache() {
+    let path = env::temp_dir().join("git-brws-test-broken-cache.json");
+    fs::write(&path, "{\"hosts\": {\"example.com\": \"unknown\"}}").unwrap();
+    let mut env = empty_env();
+    env.cache_file = Some(path.to_str().unwrap().to_string());
+    assert_eq!(Cache::load(&env), Cache::default());
+    fs::remove_file(&path).unwrap();
+
+    env.cache_file = Some("/path/to/unknown/hosts.json".to_string());
+    assert_eq!(Cache::load(&env), Cache::default());
+}
+
+#[tokio::test]
+async fn probe_request_failure() {
+    let detected = probe_service("https://unknown.endpoint.example.com", &None::<&str>)
+        .await
+        .unwrap();
+    assert_eq!(detected, None);
+}
@@ -1,5 +1,5 @@
-use crate::config::{Config, EnvConfig};
-use crate::pull_request::{find_page, Page};
+use crate::config::{Config, EnvConfig, FileConfig};
+use crate::pull_request::{find_page, find_trailer, Page};
 use crate::test::helper;
 
 macro_rules! env {
@@ -23,15 +23,22 @@ fn config(branch: Option<&str>, env: EnvConfig) -> Config {
         blame: false,        // Unused
         remote: None,        // Unused
         env,
+        file: FileConfig::default(),
     }
 }
 
 #[tokio::test]
 async fn test_find_pr_within_orig_repo() {
     let cfg = config(Some("async-eventloop"), env!());
-    let page = find_page("api.github.com", "rhysd", "vim.wasm", &cfg)
-        .await
-        .unwrap();
+    let page = find_page(
+        "api.github.com",
+        &cfg.env.github_token,
+        "rhysd",
+        "vim.wasm",
+        &cfg,
+    )
+    .await
+    .unwrap();
     assert_eq!(
         page,
         Page::Existing {
@@ -43,9 +50,15 @@ async fn test_find_pr_within_orig_repo() {
 #[tokio::test]
 async fn test_find_pr_from_fork_repo_url() {
     let cfg = config(Some("async-contextual-keyword"), env!());
-    let page = find_page("api.github.com", "rhysd", "rust.vim", &cfg)
-        .await
-        .unwrap();
+    let page = find_page(
+        "api.github.com",
+        &cfg.env.github_token,
+        "rhysd",
+        "rust.vim",
+        &cfg,
+    )
+    .await
+    .unwrap();
     assert_eq!(
         page,
         Page::Existing {
@@ -57,9 +70,15 @@ async fn test_find_pr_from_fork_repo_url() {
 #[tokio::test]
 async fn test_find_pr_from_original_repo_url() {
     let cfg = config(Some("async-contextual-keyword"), env!());
-    let page = find_page("api.github.com", "rust-lang", "rust.vim", &cfg)
-        .await
-        .unwrap();
+    let page = find_page(
+        "api.github.com",
+        &cfg.env.github_token,
+        "rust-lang",
+        "rust.vim",
+        &cfg,
+    )
+    .await
+    .unwrap();
     assert_eq!(
         page,
         Page::Existing {
@@ -71,9 +90,15 @@ async fn test_find_pr_from_original_repo_url() {
 #[tokio::test]
 async fn test_no_pr_found_at_own_repo() {
     let cfg = config(Some("unknown-branch-which-does-not-exist-for-test"), env!());
-    match find_page("api.github.com", "rhysd", "git-brws", &cfg)
-        .await
-        .unwrap()
+    match find_page(
+        "api.github.com",
+        &cfg.env.github_token,
+        "rhysd",
+        "git-brws",
+        &cfg,
+    )
+    .await
+    .unwrap()
     {
         Page::New {
             author,
@@ -91,9 +116,15 @@ async fn test_no_pr_found_at_own_repo() {
 #[tokio::test]
 async fn test_no_pr_found_at_parent_repo() {
     let cfg = config(Some("unknown-branch-which-does-not-exist-for-test"), env!());
-    match find_page("api.github.com", "rhysd", "rust.vim", &cfg)
-        .await
-        .unwrap()
+    match find_page(
+        "api.github.com",
+        &cfg.env.github_token,
+        "rhysd",
+        "rust.vim",
+        &cfg,
+    )
+    .await
+    .unwrap()
     {
         Page::NewAtParent {
             author,
@@ -109,3 +140,20 @@ async fn test_no_pr_found_at_parent_repo() {
         p => panic!("{:?}", p),
     }
 }
+
+#[test]
+fn test_find_trailer() {
+    let msg = "Fix foo\n\nChange-Id: in body is not a trailer\n\nSigned-off-by: rhysd\nChange-Id: I0123456789abcdef\n";
+    assert_eq!(find_trailer(msg, "Change-Id"), Some("I0123456789abcdef"));
+    assert_eq!(find_trailer(msg, "change-id"), Some("I0123456789abcdef"));
+    assert_eq!(find_trailer(msg, "Reviewed-by"), None);
+    assert_eq!(find_trailer("Fix foo\n\nChange-Id:\n", "Change-Id"), None);
+    assert_eq!(find_trailer("Change-Id: I0123", "Change-Id"), Some("I0123"));
+    assert_eq!(
+        find_trailer(
+            "Fix foo\n\nDifferential Revision: https://phabricator.example.com/D123",
+            "Differential Revision"
+        ),
+        Some("https://phabricator.example.com/D123"),
+    );
+}
@@ -1,7 +1,11 @@
-use crate::config::{Config, EnvConfig};
+use crate::config::{Config, EnvConfig, FileConfig};
 use crate::error::ErrorKind;
 use crate::page::{DiffOp, Line, Page};
-use crate::service::build_page_url;
+use crate::pull_request::{AzureDevOpsPage, GitLabPage, Page as PrPage};
+use crate::service::{
+    azure_devops_pr_page_url, bitbucket_pr_page_url, bitbucket_server_pr_page_url, build_page_url,
+    gitlab_mr_page_url,
+};
 use crate::test::helper::{empty_env, get_root_dir, https_proxy};
 use std::path::Path;
 
@@ -30,6 +34,7 @@ fn config(repo: &str, branch: Option<&str>, env: Option<EnvConfig>) -> Config {
         blame: false,
         remote: None,
         env: env.unwrap_or_else(empty_env),
+        file: FileConfig::default(),
     }
 }
 
@@ -50,6 +55,7 @@ fn config_for_pr(token: Option<String>, repo: &str, branch: Option<&str>) -> Con
         blame: false,
         remote: None,
         env,
+        file: FileConfig::default(),
     }
 }
 
@@ -68,11 +74,15 @@ fn build_url_from_ssh_url() {
         ),
         (
             "ssh://team@vs-ssh.visualstudio.com:22/v3/team/repo/repo.git",
-            "https://dev.azure.com/team/repo",
+            "https://team.visualstudio.com/repo/_git/repo",
         ),
         (
             "ssh://git@ssh.dev.azure.com:22/v3/team/repo/repo.git",
-            "https://dev.azure.com/team/repo",
+            "https://dev.azure.com/team/repo/_git/repo",
+        ),
+        (
+            "ssh://git@git.sr.ht:22/~user/repo",
+            "https://git.sr.ht/~user/repo",
         ),
     ] {
         let c = config(repo, None, None);
@@ -101,7 +111,23 @@ fn open_page_url() {
         ),
         (
             "https://dev.azure.com/team/repo/_git/repo",
-            "https://dev.azure.com/team/repo",
+            "https://dev.azure.com/team/repo/_git/repo",
+        ),
+        (
+            "https://codeberg.org/user/repo.git",
+            "https://codeberg.org/user/repo",
+        ),
+        (
+            "https://gitea.somewhere.com/user/repo.git",
+            "https://gitea.somewhere.com/user/repo",
+        ),
+        (
+            "https://forgejo.somewhere.com/user/repo.git",
+            "https://forgejo.somewhere.com/user/repo",
+        ),
+        (
+            "https://git.sr.ht/~user/repo",
+            "https://git.sr.ht/~user/repo",
         ),
     ] {
         let c = config(repo, None, None);
@@ -134,7 +160,15 @@ fn open_branch_page_url() {
         ),
         (
             "https://dev.azure.com/team/_git/repo",
-            "https://dev.azure.com/team/_git/repo?version=GBdev",
+            "https://dev.azure.com/team/repo/_git/repo?version=GBdev",
+        ),
+        (
+            "https://codeberg.org/user/repo.git",
+            "https://codeberg.org/user/repo/src/branch/dev",
+        ),
+        (
+            "https://git.sr.ht/~user/repo",
+            "https://git.sr.ht/~user/repo/log/dev",
         ),
     ] {
         let c = config(repo, Some("dev"), None);
@@ -166,7 +200,15 @@ fn commit_page_url() {
         ),
         (
             "https://dev.azure.com/team/_git/repo",
-            "https://dev.azure.com/team/_git/repo/commit/90601f1037142605a32426f9ece0c07d479b9cc5",
+            "https://dev.azure.com/team/repo/_git/repo/commit/90601f1037142605a32426f9ece0c07d479b9cc5",
+        ),
+        (
+            "https://codeberg.org/user/repo.git",
+            "https://codeberg.org/user/repo/commit/90601f1037142605a32426f9ece0c07d479b9cc5",
+        ),
+        (
+            "https://git.sr.ht/~user/repo",
+            "https://git.sr.ht/~user/repo/commit/90601f1037142605a32426f9ece0c07d479b9cc5",
         ),
     ] {
         let c = config(repo, None, None);
@@ -193,6 +235,10 @@ fn diff_page_url() {
                 "https://github.somewhere.com/user/repo.git",
                 format!("https://github.somewhere.com/user/repo/compare/561848bad7164d7568658456088b107ec9efd9f3{}90601f1037142605a32426f9ece0c07d479b9cc5", opstr).as_str(),
             ),
+            (
+                "https://codeberg.org/user/repo.git",
+                format!("https://codeberg.org/user/repo/compare/561848bad7164d7568658456088b107ec9efd9f3{}90601f1037142605a32426f9ece0c07d479b9cc5", opstr).as_str(),
+            ),
         ] {
             let c = config(repo, None, None);
             assert_eq!(build_page_url(&p, &c).unwrap(), expected, "for {:?}", op);
@@ -213,9 +259,9 @@ fn diff_page_for_gitlab_url() {
     let p = page(DiffOp::TwoDots);
     let u = "https://gitlab.com/user/repo.git";
     let c = config(u, None, None);
-    assert!(
-        build_page_url(&p, &c).is_err(),
-        "GitLab does not support '..' but error did not occur"
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://gitlab.com/user/repo/compare/561848bad7164d7568658456088b107ec9efd9f3...90601f1037142605a32426f9ece0c07d479b9cc5?straight=true",
     );
 
     let p = page(DiffOp::ThreeDots);
@@ -235,10 +281,20 @@ fn diff_page_for_bitbucket_url() {
         op: DiffOp::ThreeDots,
     };
     let c = config("https://bitbucket.org/user/repo", None, None);
-    assert!(
-        build_page_url(&p, &c).is_err(),
-        "bitbucket does not support diff page"
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://bitbucket.org/user/repo/branches/compare/90601f1037142605a32426f9ece0c07d479b9cc5%0D561848bad7164d7568658456088b107ec9efd9f3",
     );
+
+    let p = Page::Diff {
+        lhs: "561848bad7164d7568658456088b107ec9efd9f3".to_string(),
+        rhs: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+        op: DiffOp::TwoDots,
+    };
+    match build_page_url(&p, &c).unwrap_err().kind() {
+        ErrorKind::BitbucketDiffNotSupported => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
 }
 
 #[test]
@@ -249,10 +305,162 @@ fn diff_page_for_azuredevops_url() {
         op: DiffOp::ThreeDots,
     };
     let c = config("https://dev.azure.com/team/repo/_git/repo", None, None);
-    assert!(
-        build_page_url(&p, &c).is_err(),
-        "azure devops does not support diff page"
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://dev.azure.com/team/repo/_git/repo/branchCompare?baseVersion=GC561848bad7164d7568658456088b107ec9efd9f3&targetVersion=GC90601f1037142605a32426f9ece0c07d479b9cc5",
+    );
+
+    let p = Page::Diff {
+        lhs: "561848bad7164d7568658456088b107ec9efd9f3".to_string(),
+        rhs: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+        op: DiffOp::TwoDots,
+    };
+    match build_page_url(&p, &c).unwrap_err().kind() {
+        ErrorKind::AzureDevOpsNotSupported { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn azuredevops_organization_project_and_repo() {
+    for &(repo, expected) in &[
+        (
+            "https://dev.azure.com/org/project/_git/repo",
+            "https://dev.azure.com/org/project/_git/repo",
+        ),
+        (
+            "https://org@dev.azure.com/org/project/_git/repo",
+            "https://dev.azure.com/org/project/_git/repo",
+        ),
+        (
+            "https://dev.azure.com/org/_git/repo",
+            "https://dev.azure.com/org/repo/_git/repo",
+        ),
+        (
+            "https://dev.azure.com/org/project",
+            "https://dev.azure.com/org/project/_git/project",
+        ),
+        (
+            "ssh://git@ssh.dev.azure.com:22/v3/org/project/repo",
+            "https://dev.azure.com/org/project/_git/repo",
+        ),
+        (
+            "https://org.visualstudio.com/project/_git/repo",
+            "https://org.visualstudio.com/project/_git/repo",
+        ),
+        (
+            "https://org.visualstudio.com/DefaultCollection/project/_git/repo",
+            "https://org.visualstudio.com/project/_git/repo",
+        ),
+        (
+            "ssh://org@vs-ssh.visualstudio.com:22/v3/org/project/repo",
+            "https://org.visualstudio.com/project/_git/repo",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        assert_eq!(build_page_url(&OPEN, &c).unwrap(), expected);
+    }
+
+    let c = config("https://dev.azure.com/org/project/_git/repo", None, None);
+    assert_eq!(
+        build_page_url(&Page::Issue { number: 12 }, &c).unwrap(),
+        "https://dev.azure.com/org/project/_workitems/edit/12",
     );
+
+    for &repo in &[
+        "https://dev.azure.com/org",
+        "https://dev.azure.com/org/project/_git",
+        "ssh://git@ssh.dev.azure.com:22/v3/org/project",
+    ] {
+        let c = config(repo, None, None);
+        match build_page_url(&OPEN, &c).unwrap_err().kind() {
+            ErrorKind::NoRepoInPath { .. } => { /* ok */ }
+            e => panic!("Unexpected error for {}: {:?}", repo, e),
+        }
+    }
+}
+
+#[test]
+fn file_pages_for_azuredevops_url() {
+    let base = "https://dev.azure.com/team/repo/_git/repo?path=/src/main.rs";
+    let hash = "561848bad7164d7568658456088b107ec9efd9f3";
+    for (page, expected) in [
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: hash.to_string(),
+                line: None,
+                blame: false,
+                is_dir: false,
+            },
+            format!("&version=GC{}", hash),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "dev".to_string(),
+                line: None,
+                blame: false,
+                is_dir: false,
+            },
+            "&version=GBdev".to_string(),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: hash.to_string(),
+                line: Some(Line::At(10)),
+                blame: false,
+                is_dir: false,
+            },
+            format!(
+                "&version=GC{}&line=10&lineEnd=10&lineStartColumn=1&lineEndColumn=1&lineStyle=plain",
+                hash,
+            ),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: hash.to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            format!(
+                "&version=GC{}&line=10&lineEnd=20&lineStartColumn=1&lineEndColumn=1&lineStyle=plain",
+                hash,
+            ),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: hash.to_string(),
+                line: None,
+                blame: true,
+                is_dir: false,
+            },
+            format!("&version=GC{}&_a=blame", hash),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: hash.to_string(),
+                line: Some(Line::At(10)),
+                blame: true,
+                is_dir: false,
+            },
+            format!(
+                "&version=GC{}&line=10&lineEnd=10&lineStartColumn=1&lineEndColumn=1&lineStyle=plain&_a=blame",
+                hash,
+            ),
+        ),
+    ] {
+        let c = config("https://dev.azure.com/team/repo/_git/repo", None, None);
+        assert_eq!(
+            build_page_url(&page, &c).unwrap(),
+            format!("{}{}", base, expected)
+        );
+    }
 }
 
 #[test]
@@ -280,7 +488,7 @@ fn file_page_url() {
         blame_no_line,
         blame_with_line,
         blame_with_range,
-    ) in vec![
+    ) in [
         (
             "https://github.com/user/repo.git",
             "https://github.com/user/repo/blob/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs",
@@ -317,6 +525,24 @@ fn file_page_url() {
             "https://gitlab.com/user/repo/blame/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L12",
             "https://gitlab.com/user/repo/blame/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L1-L2",
         ),
+        (
+            "https://codeberg.org/user/repo.git",
+            "https://codeberg.org/user/repo/src/commit/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs",
+            "https://codeberg.org/user/repo/src/commit/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L12",
+            "https://codeberg.org/user/repo/src/commit/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L1-L2",
+            "https://codeberg.org/user/repo/blame/commit/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs",
+            "https://codeberg.org/user/repo/blame/commit/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L12",
+            "https://codeberg.org/user/repo/blame/commit/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L1-L2",
+        ),
+        (
+            "https://git.sr.ht/~user/repo",
+            "https://git.sr.ht/~user/repo/tree/561848bad7164d7568658456088b107ec9efd9f3/item/src/main.rs",
+            "https://git.sr.ht/~user/repo/tree/561848bad7164d7568658456088b107ec9efd9f3/item/src/main.rs#L12",
+            "https://git.sr.ht/~user/repo/tree/561848bad7164d7568658456088b107ec9efd9f3/item/src/main.rs#L1-2",
+            "https://git.sr.ht/~user/repo/blame/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs",
+            "https://git.sr.ht/~user/repo/blame/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L12",
+            "https://git.sr.ht/~user/repo/blame/561848bad7164d7568658456088b107ec9efd9f3/src/main.rs#L1-2",
+        ),
     ] {
         let c = config(repo, None, None);
 
@@ -370,6 +596,10 @@ fn directory_page_url() {
             "https://gitlab.com/user/repo.git",
             "https://gitlab.com/user/repo/tree/561848bad7164d7568658456088b107ec9efd9f3/src",
         ),
+        (
+            "https://codeberg.org/user/repo.git",
+            "https://codeberg.org/user/repo/src/commit/561848bad7164d7568658456088b107ec9efd9f3/src",
+        ),
     ] {
         let config = config(repo, None, None);
         let url = build_page_url(&page, &config).unwrap();
@@ -395,10 +625,7 @@ fn invalid_repo_url() {
 
 #[test]
 fn customized_ssh_port() {
-    let mut env = empty_env();
-    env.ghe_ssh_port = Some(10022);
-    env.gitlab_ssh_port = Some(10022);
-
+    // SSH port in remote URL is not a port of web pages
     for &(repo, expected) in &[
         (
             "https://github.com/user/repo.git",
@@ -406,18 +633,22 @@ fn customized_ssh_port() {
         ),
         (
             "https://github.somewhere.com/user/repo.git",
-            "https://github.somewhere.com:10022/user/repo",
+            "https://github.somewhere.com/user/repo",
+        ),
+        (
+            "ssh://git@github.somewhere.com:10022/user/repo.git",
+            "https://github.somewhere.com/user/repo",
         ),
         (
             "https://gitlab.com/user/repo.git",
             "https://gitlab.com/user/repo",
         ),
         (
-            "https://gitlab.somewhere.com/user/repo.git",
-            "https://gitlab.somewhere.com:10022/user/repo",
+            "ssh://git@gitlab.somewhere.com:10022/user/repo.git",
+            "https://gitlab.somewhere.com/user/repo",
         ),
     ] {
-        let c = config(repo, None, Some(env.clone()));
+        let c = config(repo, None, None);
         assert_eq!(build_page_url(&OPEN, &c).unwrap(), expected.to_string(),);
     }
 }
@@ -427,20 +658,64 @@ fn customized_ghe_host() {
     let mut env = empty_env();
     env.ghe_url_host = Some("my-original-ghe.org".to_string());
 
-    for (port, expected) in &[
-        (None, "https://my-original-ghe.org/user/repo"),
-        (Some(10022), "https://my-original-ghe.org:10022/user/repo"),
+    for repo in &[
+        "https://my-original-ghe.org/user/repo.git",
+        "ssh://git@my-original-ghe.org:10022/user/repo.git",
     ] {
-        env.ghe_ssh_port = *port;
-        let c = config(
-            "https://my-original-ghe.org/user/repo.git",
-            None,
-            Some(env.clone()),
+        let c = config(repo, None, Some(env.clone()));
+        assert_eq!(
+            build_page_url(&OPEN, &c).unwrap(),
+            "https://my-original-ghe.org/user/repo",
         );
-        assert_eq!(build_page_url(&OPEN, &c).unwrap(), expected.to_string(),);
     }
 }
 
+#[test]
+fn scheme_and_port_of_remote_url() {
+    for &(repo, expected) in &[
+        (
+            "https://github.somewhere.com:8443/user/repo.git",
+            "https://github.somewhere.com:8443/user/repo/commit/0123456",
+        ),
+        (
+            "http://gitlab.somewhere.com/user/repo.git",
+            "http://gitlab.somewhere.com/user/repo/commit/0123456",
+        ),
+        (
+            "http://gitea.somewhere.com:3000/user/repo.git",
+            "http://gitea.somewhere.com:3000/user/repo/commit/0123456",
+        ),
+        (
+            "ssh://git@gitlab.somewhere.com:2222/user/repo.git",
+            "https://gitlab.somewhere.com/user/repo/commit/0123456",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        let page = Page::Commit {
+            hash: "0123456".to_string(),
+        };
+        assert_eq!(build_page_url(&page, &c).unwrap(), expected);
+    }
+
+    // Explicit settings in config file are prioritized over remote URL
+    let file = FileConfig::parse(
+        r#"
+[[hosts]]
+host = "code.example.com"
+kind = "gitlab"
+scheme = "https"
+port = 8443
+"#,
+    )
+    .unwrap();
+    let mut c = config("http://code.example.com:8080/user/repo.git", None, None);
+    c.file = file;
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://code.example.com:8443/user/repo",
+    );
+}
+
 #[test]
 fn customized_gitlab_host() {
     let mut env = empty_env();
@@ -458,90 +733,1309 @@ fn customized_gitlab_host() {
 }
 
 #[test]
-fn broken_repo_url() {
-    let env = &empty_env();
-    for &url in &[
-        "https://foo@/foo.bar", // empty host
-        "https://foo bar",      // invalid domain character
-    ] {
-        let c = config(url, None, Some(env.clone()));
-        match build_page_url(&OPEN, &c).unwrap_err().kind() {
-            ErrorKind::BrokenUrl { .. } => { /* ok */ }
-            e => panic!("Unexpected error: {:?}", e),
-        }
+fn customized_gitea_host() {
+    let mut env = empty_env();
+    env.gitea_url_host = Some("my-forgejo.example.com".to_string());
+
+    let c = config(
+        "https://my-forgejo.example.com/user/repo.git",
+        Some("dev"),
+        Some(env),
+    );
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://my-forgejo.example.com/user/repo/src/branch/dev"
+    );
+}
+
+#[test]
+fn file_page_at_branch_for_gitea() {
+    let page = Page::FilePath {
+        relative_path: "src/main.rs".to_string(),
+        hash: "dev".to_string(),
+        line: Some(Line::At(3)),
+        blame: false,
+        is_dir: false,
+    };
+    let c = config("ssh://git@codeberg.org:22/user/repo.git", None, None);
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://codeberg.org/user/repo/src/branch/dev/src/main.rs#L3"
+    );
+}
+
+#[test]
+fn tag_page_for_gitea() {
+    let page = Page::Tag {
+        tagname: "v1.0.0".to_string(),
+        commit: "01234cdef".to_string(),
+    };
+    let c = config("https://codeberg.org/user/repo.git", None, None);
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://codeberg.org/user/repo/src/tag/v1.0.0"
+    );
+}
+
+#[test]
+fn diff_page_for_sourcehut_url() {
+    let p = Page::Diff {
+        lhs: "561848bad7164d7568658456088b107ec9efd9f3".to_string(),
+        rhs: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+        op: DiffOp::ThreeDots,
+    };
+    let c = config("https://git.sr.ht/~user/repo", None, None);
+    match build_page_url(&p, &c).unwrap_err().kind() {
+        ErrorKind::SourceHutDiffNotSupported => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
     }
 }
 
 #[test]
-fn issue_number_url() {
-    let p = Page::Issue { number: 123 };
+fn tag_page_for_sourcehut() {
+    let page = Page::Tag {
+        tagname: "v1.0.0".to_string(),
+        commit: "01234cdef".to_string(),
+    };
+    let c = config("ssh://git@git.sr.ht:22/~user/repo", None, None);
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://git.sr.ht/~user/repo/refs/v1.0.0"
+    );
+}
+
+#[test]
+fn sourcehut_user_without_tilde() {
+    let c = config("https://git.sr.ht/user/repo", None, None);
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::NoUserInPath { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn pull_request_page_for_sourcehut() {
+    let mut c = config("https://git.sr.ht/~user/repo", None, None);
+    c.cwd = get_root_dir();
+    assert_eq!(
+        build_page_url(&OPEN_PR, &c).unwrap(),
+        "https://lists.sr.ht/~user/repo/patches"
+    );
+}
+
+fn bitbucket_server_env() -> EnvConfig {
+    let mut env = empty_env();
+    env.bitbucket_server_url_host = Some("code.example.com".to_string());
+    env
+}
+
+#[test]
+fn bitbucket_server_clone_urls() {
     for &(repo, expected) in &[
         (
-            "https://github.com/user/repo.git",
-            "https://github.com/user/repo/issues/123",
+            "https://code.example.com/scm/proj/repo.git",
+            "https://code.example.com/projects/PROJ/repos/repo/browse",
         ),
         (
-            "https://bitbucket.org/user/repo.git",
-            "https://bitbucket.org/user/repo/issues/123",
+            "ssh://git@code.example.com:7999/proj/repo.git",
+            "https://code.example.com/projects/PROJ/repos/repo/browse",
         ),
         (
-            "https://github.somewhere.com/user/repo.git",
-            "https://github.somewhere.com/user/repo/issues/123",
+            "https://code.example.com/scm/~user/repo.git",
+            "https://code.example.com/users/user/repos/repo/browse",
         ),
         (
-            "https://gitlab.com/user/repo.git",
-            "https://gitlab.com/user/repo/issues/123",
+            "ssh://git@code.example.com:7999/~user/repo.git",
+            "https://code.example.com/users/user/repos/repo/browse",
         ),
         (
-            "https://dev.azure.com/team/repo/_git/repo",
-            "https://dev.azure.com/team/repo/_workitems/edit/123",
+            "https://bitbucket.somewhere.com/scm/proj/repo.git",
+            "https://bitbucket.somewhere.com/projects/PROJ/repos/repo/browse",
+        ),
+        (
+            "http://code.example.com:7990/scm/proj/repo.git",
+            "http://code.example.com:7990/projects/PROJ/repos/repo/browse",
+        ),
+        (
+            "https://code.example.com:8443/scm/~user/repo.git",
+            "https://code.example.com:8443/users/user/repos/repo/browse",
         ),
     ] {
-        let c = config(repo, None, None);
-        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
-    }
-}
-
-#[test]
-fn unknown_github_enterprise_url() {
-    let mut env = empty_env();
-    env.ghe_url_host = Some("github-yourcompany.com".to_string());
-    let c = config(
-        "https://github-othercompany.com/foo/bar.git",
-        None,
-        Some(env),
-    );
-    match build_page_url(&OPEN, &c).unwrap_err().kind() {
-        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
-        err => panic!("Unexpected error: {}", err),
-    }
-
-    let mut c = config_for_pr(None, "https://github-othercompany.com/foo/bar.git", None);
-    c.env.ghe_url_host = Some("github-yourcompany.com".to_string());
-    match build_page_url(&OPEN_PR, &c).unwrap_err().kind() {
-        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
-        err => panic!("Unexpected error: {}", err),
+        let c = config(repo, None, Some(bitbucket_server_env()));
+        assert_eq!(build_page_url(&OPEN, &c).unwrap(), expected);
     }
 }
 
 #[test]
-fn website_github_pages() {
-    let mut env = empty_env();
-    env.github_token = skip_if_no_token!();
-    env.https_proxy = https_proxy();
-    let env = env;
-    let testcases = &[
+fn bitbucket_server_pages() {
+    let repo = "ssh://git@code.example.com:7999/proj/repo.git";
+    let base = "https://code.example.com/projects/PROJ/repos/repo";
+    for (page, expected) in [
         (
-            "https://github.com/rhysd/git-brws.git", // With gh-pages
-            "https://rhysd.github.io/git-brws/",
+            Page::Commit {
+                hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+            },
+            "/commits/90601f1037142605a32426f9ece0c07d479b9cc5",
         ),
         (
-            "https://github.com/rhysd/dogfiles", // Without gh-pages, but with homepage
-            "https://rhysd.github.io",
+            Page::Diff {
+                lhs: "561848b".to_string(),
+                rhs: "90601f1".to_string(),
+                op: DiffOp::ThreeDots,
+            },
+            "/compare/diff?sourceBranch=90601f1&targetBranch=561848b",
         ),
         (
-            "https://github.com/rhysd/filter-with-state", // Without homepage
-            "https://rhysd.github.io/filter-with-state",
+            Page::Tag {
+                tagname: "v1.0.0".to_string(),
+                commit: "01234cdef".to_string(),
+            },
+            "/browse?at=refs/tags/v1.0.0",
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "561848b".to_string(),
+                line: None,
+                blame: false,
+                is_dir: false,
+            },
+            "/browse/src/main.rs?at=561848b",
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "561848b".to_string(),
+                line: Some(Line::At(10)),
+                blame: false,
+                is_dir: false,
+            },
+            "/browse/src/main.rs?at=561848b#10",
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "561848b".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            "/browse/src/main.rs?at=561848b#10-20",
+        ),
+    ] {
+        let c = config(repo, None, Some(bitbucket_server_env()));
+        assert_eq!(
+            build_page_url(&page, &c).unwrap(),
+            format!("{}{}", base, expected)
+        );
+    }
+
+    let c = config(repo, Some("dev"), Some(bitbucket_server_env()));
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        format!("{}/browse?at=refs/heads/dev", base)
+    );
+}
+
+#[test]
+fn bitbucket_server_unsupported_pages() {
+    for page in &[
+        Page::Issue { number: 1 },
+        Page::Diff {
+            lhs: "561848b".to_string(),
+            rhs: "90601f1".to_string(),
+            op: DiffOp::TwoDots,
+        },
+        Page::FilePath {
+            relative_path: "src/main.rs".to_string(),
+            hash: "561848b".to_string(),
+            line: None,
+            blame: true,
+            is_dir: false,
+        },
+    ] {
+        let c = config(
+            "https://code.example.com/scm/proj/repo.git",
+            None,
+            Some(bitbucket_server_env()),
+        );
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::BitbucketServerNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error for {:?}: {:?}", page, e),
+        }
+    }
+}
+
+#[test]
+fn codecommit_pages() {
+    let base = "https://us-east-1.console.aws.amazon.com/codesuite/codecommit/repositories/repo";
+    for (page, expected) in [
+        (OPEN, "/browse?region=us-east-1"),
+        (OPEN_PR, "/pull-requests?region=us-east-1&status=OPEN"),
+        (
+            Page::Commit {
+                hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+            },
+            "/commit/90601f1037142605a32426f9ece0c07d479b9cc5?region=us-east-1",
+        ),
+        (
+            Page::Diff {
+                lhs: "561848b".to_string(),
+                rhs: "90601f1".to_string(),
+                op: DiffOp::ThreeDots,
+            },
+            "/compare/561848b/.../90601f1?region=us-east-1",
+        ),
+        (
+            Page::Tag {
+                tagname: "v1.0.0".to_string(),
+                commit: "01234cdef".to_string(),
+            },
+            "/browse/refs/tags/v1.0.0?region=us-east-1",
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "561848b".to_string(),
+                line: None,
+                blame: false,
+                is_dir: false,
+            },
+            "/browse/561848b/--/src/main.rs?region=us-east-1",
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "561848b".to_string(),
+                line: Some(Line::At(10)),
+                blame: false,
+                is_dir: false,
+            },
+            "/browse/561848b/--/src/main.rs?region=us-east-1&lines=10-10",
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "dev".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            "/browse/refs/heads/dev/--/src/main.rs?region=us-east-1&lines=10-20",
+        ),
+    ] {
+        for repo in &[
+            "https://git-codecommit.us-east-1.amazonaws.com/v1/repos/repo.git",
+            "ssh://git-codecommit.us-east-1.amazonaws.com/v1/repos/repo",
+            "ssh://APKAEIBAERJR2EXAMPLE@git-codecommit.us-east-1.amazonaws.com/v1/repos/repo",
+            "https://git-codecommit-fips.us-east-1.amazonaws.com/v1/repos/repo",
+        ] {
+            let c = config(repo, None, None);
+            assert_eq!(
+                build_page_url(&page, &c).unwrap(),
+                format!("{}{}", base, expected)
+            );
+        }
+    }
+
+    let c = config(
+        "https://git-codecommit.eu-west-1.amazonaws.com/v1/repos/repo",
+        Some("dev"),
+        None,
+    );
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://eu-west-1.console.aws.amazon.com/codesuite/codecommit/repositories/repo/browse/refs/heads/dev?region=eu-west-1"
+    );
+}
+
+#[test]
+fn codecommit_errors() {
+    let c = config("codecommit://repo", None, None);
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::BrokenUrl { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+
+    let c = config(
+        "https://git-codecommit.us-east-1.amazonaws.com/v2/foo/repo",
+        None,
+        None,
+    );
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::NoRepoInPath { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+
+    let c = config(
+        "https://git-codecommit.us-east-1.amazonaws.com/v1/repos/repo",
+        None,
+        None,
+    );
+    match build_page_url(&Page::Issue { number: 1 }, &c)
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::CodeCommitNotSupported { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn gerrit_pages() {
+    let mut env = empty_env();
+    env.gerrit_url_host = Some("review.example.com".to_string());
+    let b = "https://go.googlesource.com/tools";
+    let self_hosted = "https://review.example.com/plugins/gitiles/foo/bar";
+    for (repo, page, branch, expected) in &[
+        (
+            "https://go.googlesource.com/tools",
+            OPEN,
+            None,
+            b.to_string(),
+        ),
+        (
+            "https://go-review.googlesource.com/a/tools.git",
+            OPEN,
+            None,
+            b.to_string(),
+        ),
+        (
+            "ssh://user@review.example.com:29418/foo/bar.git",
+            OPEN,
+            None,
+            self_hosted.to_string(),
+        ),
+        (
+            "https://go.googlesource.com/tools",
+            OPEN,
+            Some("dev"),
+            format!("{}/+log/refs/heads/dev", b),
+        ),
+        (
+            "https://go.googlesource.com/tools",
+            Page::Commit {
+                hash: "0123456".to_string(),
+            },
+            None,
+            format!("{}/+/0123456", b),
+        ),
+        (
+            "https://go.googlesource.com/tools",
+            Page::Tag {
+                tagname: "v1.0.0".to_string(),
+                commit: "0123456".to_string(),
+            },
+            None,
+            format!("{}/+/refs/tags/v1.0.0", b),
+        ),
+        (
+            "https://go.googlesource.com/tools",
+            Page::Diff {
+                lhs: "0123456".to_string(),
+                rhs: "789abcd".to_string(),
+                op: DiffOp::TwoDots,
+            },
+            None,
+            format!("{}/+/0123456..789abcd", b),
+        ),
+        (
+            "https://go.googlesource.com/tools",
+            Page::FilePath {
+                relative_path: "src/main.go".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            None,
+            format!("{}/+/0123456/src/main.go#10", b),
+        ),
+        (
+            "https://go.googlesource.com/tools",
+            Page::FilePath {
+                relative_path: "src/main.go".to_string(),
+                hash: "0123456".to_string(),
+                line: None,
+                blame: true,
+                is_dir: false,
+            },
+            None,
+            format!("{}/+blame/0123456/src/main.go", b),
+        ),
+    ] {
+        let c = config(repo, *branch, Some(env.clone()));
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn gerrit_with_port() {
+    let c = config("https://gerrit.example.com:8443/a/proj", None, None);
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://gerrit.example.com:8443/plugins/gitiles/proj",
+    );
+}
+
+#[test]
+fn gerrit_unsupported_pages() {
+    for page in &[
+        OPEN_WEBSITE,
+        Page::Issue { number: 1 },
+        Page::Diff {
+            lhs: "0123456".to_string(),
+            rhs: "789abcd".to_string(),
+            op: DiffOp::ThreeDots,
+        },
+    ] {
+        let c = config("https://go.googlesource.com/tools", None, None);
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::GerritNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn cgit_pages() {
+    let mut env = empty_env();
+    env.cgit_url_host = Some("git.kernel.org".to_string());
+    let b = "https://git.kernel.org/pub/scm/git/git.git";
+    for (page, branch, expected) in &[
+        (OPEN, None, b.to_string()),
+        (OPEN, Some("next"), format!("{}/log/?h=next", b)),
+        (
+            Page::Commit {
+                hash: "0123456".to_string(),
+            },
+            None,
+            format!("{}/commit/?id=0123456", b),
+        ),
+        (
+            Page::Tag {
+                tagname: "v2.0.0".to_string(),
+                commit: "0123456".to_string(),
+            },
+            None,
+            format!("{}/tag/?h=v2.0.0", b),
+        ),
+        (
+            Page::Diff {
+                lhs: "0123456".to_string(),
+                rhs: "789abcd".to_string(),
+                op: DiffOp::TwoDots,
+            },
+            None,
+            format!("{}/diff/?id=789abcd&id2=0123456", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.c".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            None,
+            format!("{}/tree/src/main.c?id=0123456#n10", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.c".to_string(),
+                hash: "0123456".to_string(),
+                line: None,
+                blame: true,
+                is_dir: false,
+            },
+            None,
+            format!("{}/blame/src/main.c?id=0123456", b),
+        ),
+    ] {
+        let c = config(
+            "git://git.kernel.org/pub/scm/git/git.git",
+            *branch,
+            Some(env.clone()),
+        );
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn cgit_with_port() {
+    let mut env = empty_env();
+    env.cgit_url_host = Some("git.example.com".to_string());
+    let c = config("http://git.example.com:8080/repo.git", None, Some(env));
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "http://git.example.com:8080/repo.git",
+    );
+}
+
+#[test]
+fn gitweb_pages() {
+    let mut env = empty_env();
+    env.gitweb_url_host = Some("repo.or.cz".to_string());
+    let b = "https://repo.or.cz/?p=tinycc.git";
+    for (page, branch, expected) in &[
+        (OPEN, None, format!("{};a=summary", b)),
+        (
+            OPEN,
+            Some("mob"),
+            format!("{};a=shortlog;h=refs/heads/mob", b),
+        ),
+        (
+            Page::Commit {
+                hash: "0123456".to_string(),
+            },
+            None,
+            format!("{};a=commit;h=0123456", b),
+        ),
+        (
+            Page::Tag {
+                tagname: "v0.9.27".to_string(),
+                commit: "0123456".to_string(),
+            },
+            None,
+            format!("{};a=shortlog;h=refs/tags/v0.9.27", b),
+        ),
+        (
+            Page::Diff {
+                lhs: "0123456".to_string(),
+                rhs: "789abcd".to_string(),
+                op: DiffOp::TwoDots,
+            },
+            None,
+            format!("{};a=commitdiff;h=789abcd;hp=0123456", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "tcc.c".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::At(10)),
+                blame: false,
+                is_dir: false,
+            },
+            None,
+            format!("{};a=blob;f=tcc.c;hb=0123456#l10", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "lib".to_string(),
+                hash: "0123456".to_string(),
+                line: None,
+                blame: false,
+                is_dir: true,
+            },
+            None,
+            format!("{};a=tree;f=lib;hb=0123456", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "tcc.c".to_string(),
+                hash: "0123456".to_string(),
+                line: None,
+                blame: true,
+                is_dir: false,
+            },
+            None,
+            format!("{};a=blame;f=tcc.c;hb=0123456", b),
+        ),
+    ] {
+        let c = config("https://repo.or.cz/tinycc.git", *branch, Some(env.clone()));
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn gitweb_with_port() {
+    let mut env = empty_env();
+    env.gitweb_url_host = Some("git.example.com".to_string());
+    let c = config("http://git.example.com:8080/repo.git", None, Some(env));
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "http://git.example.com:8080/?p=repo.git;a=summary",
+    );
+}
+
+#[test]
+fn cgit_and_gitweb_unsupported_pages() {
+    let mut env = empty_env();
+    env.cgit_url_host = Some("git.kernel.org".to_string());
+    env.gitweb_url_host = Some("repo.or.cz".to_string());
+    for page in &[
+        OPEN_WEBSITE,
+        Page::Issue { number: 1 },
+        Page::Diff {
+            lhs: "0123456".to_string(),
+            rhs: "789abcd".to_string(),
+            op: DiffOp::ThreeDots,
+        },
+    ] {
+        let c = config(
+            "https://git.kernel.org/pub/scm/git/git.git",
+            None,
+            Some(env.clone()),
+        );
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::CgitNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        let c = config("https://repo.or.cz/tinycc.git", None, Some(env.clone()));
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::GitWebNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    let c = config("https://repo.or.cz/tinycc.git", None, Some(env));
+    match build_page_url(&OPEN_PR, &c).unwrap_err().kind() {
+        ErrorKind::PullReqNotSupported { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn phabricator_pages() {
+    let b = "https://phabricator.example.com/source/repo";
+    for (repo, page, branch, expected) in &[
+        (
+            "https://phabricator.example.com/source/repo.git",
+            OPEN,
+            None,
+            format!("{}/", b),
+        ),
+        (
+            "ssh://git@phabricator.example.com/diffusion/R/repo.git",
+            OPEN,
+            Some("dev"),
+            "https://phabricator.example.com/diffusion/R/browse/dev/".to_string(),
+        ),
+        (
+            "https://phabricator.example.com/source/repo.git",
+            Page::Commit {
+                hash: "0123456".to_string(),
+            },
+            None,
+            format!("{}/commit/0123456/", b),
+        ),
+        (
+            "https://phabricator.example.com/source/repo.git",
+            Page::Diff {
+                lhs: "0123456".to_string(),
+                rhs: "789abcd".to_string(),
+                op: DiffOp::ThreeDots,
+            },
+            None,
+            format!("{}/compare/?head=789abcd&against=0123456", b),
+        ),
+        (
+            "https://phabricator.example.com/source/repo.git",
+            Page::FilePath {
+                relative_path: "src/main.c".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            Some("dev"),
+            format!("{}/browse/dev/src/main.c;0123456$10-20", b),
+        ),
+        (
+            "https://phabricator.example.com/source/repo.git",
+            Page::FilePath {
+                relative_path: "src/main.c".to_string(),
+                hash: "dev".to_string(),
+                line: Some(Line::At(10)),
+                blame: true,
+                is_dir: false,
+            },
+            Some("dev"),
+            format!("{}/browse/dev/src/main.c$10?blame=1", b),
+        ),
+        (
+            "https://phabricator.example.com/source/repo.git",
+            Page::Issue { number: 12 },
+            None,
+            "https://phabricator.example.com/T12".to_string(),
+        ),
+    ] {
+        let c = config(repo, *branch, None);
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn phabricator_with_port() {
+    let c = config(
+        "https://phabricator.example.com:8443/source/repo.git",
+        None,
+        None,
+    );
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://phabricator.example.com:8443/source/repo/",
+    );
+    assert_eq!(
+        build_page_url(&Page::Issue { number: 12 }, &c).unwrap(),
+        "https://phabricator.example.com:8443/T12",
+    );
+}
+
+#[test]
+fn phabricator_errors() {
+    let c = config(
+        "https://phabricator.example.com/source/repo.git",
+        None,
+        None,
+    );
+    for page in &[
+        OPEN_WEBSITE,
+        Page::Diff {
+            lhs: "0123456".to_string(),
+            rhs: "789abcd".to_string(),
+            op: DiffOp::TwoDots,
+        },
+    ] {
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::PhabricatorNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    let mut env = empty_env();
+    env.phabricator_url_host = Some("code.example.com".to_string());
+    let c = config("https://code.example.com/user/repo.git", None, Some(env));
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::NoRepoInPath { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn pagure_pages() {
+    let b = "https://src.fedoraproject.org/rpms/bash";
+    for (repo, page, expected) in &[
+        (
+            "https://src.fedoraproject.org/rpms/bash.git",
+            OPEN,
+            b.to_string(),
+        ),
+        (
+            "ssh://user@pkgs.fedoraproject.org/rpms/bash",
+            OPEN,
+            b.to_string(),
+        ),
+        (
+            "ssh://git@pagure.io/forks/user/pagure.git",
+            OPEN,
+            "https://pagure.io/fork/user/pagure".to_string(),
+        ),
+        (
+            "https://src.fedoraproject.org/rpms/bash.git",
+            Page::Commit {
+                hash: "0123456".to_string(),
+            },
+            format!("{}/c/0123456", b),
+        ),
+        (
+            "https://src.fedoraproject.org/rpms/bash.git",
+            Page::Tag {
+                tagname: "v1.0".to_string(),
+                commit: "0123456".to_string(),
+            },
+            format!("{}/tree/v1.0", b),
+        ),
+        (
+            "https://src.fedoraproject.org/rpms/bash.git",
+            Page::Diff {
+                lhs: "rawhide".to_string(),
+                rhs: "topic".to_string(),
+                op: DiffOp::ThreeDots,
+            },
+            format!("{}/diff/rawhide..topic", b),
+        ),
+        (
+            "https://src.fedoraproject.org/rpms/bash.git",
+            Page::FilePath {
+                relative_path: "bash.spec".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            format!("{}/blob/0123456/f/bash.spec#_10", b),
+        ),
+        (
+            "https://src.fedoraproject.org/rpms/bash.git",
+            Page::FilePath {
+                relative_path: "bash.spec".to_string(),
+                hash: "0123456".to_string(),
+                line: None,
+                blame: true,
+                is_dir: false,
+            },
+            format!("{}/blame/bash.spec?identifier=0123456", b),
+        ),
+        (
+            "https://src.fedoraproject.org/rpms/bash.git",
+            Page::Issue { number: 3 },
+            format!("{}/issue/3", b),
+        ),
+    ] {
+        let c = config(repo, None, None);
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn launchpad_pages() {
+    let b = "https://git.launchpad.net/~user/project/+git/repo";
+    for (repo, page, expected) in &[
+        (
+            "git+ssh://user@git.launchpad.net/~user/project/+git/repo",
+            OPEN,
+            format!("{}/log/?h=topic", b),
+        ),
+        (
+            "https://git.launchpad.net/ubuntu/+source/bash",
+            OPEN,
+            "https://git.launchpad.net/ubuntu/+source/bash/log/?h=topic".to_string(),
+        ),
+        (
+            "https://git.launchpad.net/~user/project/+git/repo",
+            Page::FilePath {
+                relative_path: "src/main.c".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::At(3)),
+                blame: false,
+                is_dir: false,
+            },
+            format!("{}/tree/src/main.c?id=0123456#n3", b),
+        ),
+        (
+            "https://git.launchpad.net/~user/project/+git/repo",
+            Page::Diff {
+                lhs: "0123456".to_string(),
+                rhs: "789abcd".to_string(),
+                op: DiffOp::TwoDots,
+            },
+            format!("{}/diff/?id=789abcd&id2=0123456", b),
+        ),
+        (
+            "https://git.launchpad.net/~user/project/+git/repo",
+            Page::Issue { number: 1234 },
+            "https://bugs.launchpad.net/bugs/1234".to_string(),
+        ),
+        (
+            "https://git.launchpad.net/~user/project/+git/repo",
+            OPEN_PR,
+            "https://code.launchpad.net/~user/project/+git/repo/+ref/topic".to_string(),
+        ),
+        (
+            "https://git.launchpad.net/ubuntu/+source/bash",
+            OPEN_PR,
+            "https://code.launchpad.net/ubuntu/+source/bash/+activereviews".to_string(),
+        ),
+    ] {
+        let c = config(repo, Some("topic"), None);
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn pagure_with_port() {
+    let mut env = empty_env();
+    env.pagure_url_host = Some("pagure.example.com".to_string());
+    let c = config("http://pagure.example.com:8080/repo.git", None, Some(env));
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "http://pagure.example.com:8080/repo",
+    );
+}
+
+#[test]
+fn pagure_and_launchpad_unsupported_pages() {
+    let c = config("https://pagure.io/pagure.git", None, None);
+    for page in &[
+        OPEN_WEBSITE,
+        Page::Diff {
+            lhs: "0123456".to_string(),
+            rhs: "789abcd".to_string(),
+            op: DiffOp::TwoDots,
+        },
+    ] {
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::PagureNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    let c = config("https://git.launchpad.net/project", None, None);
+    for page in &[
+        OPEN_WEBSITE,
+        Page::Diff {
+            lhs: "0123456".to_string(),
+            rhs: "789abcd".to_string(),
+            op: DiffOp::ThreeDots,
+        },
+    ] {
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::LaunchpadNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
+
+fn config_with_templates(repo: &str, branch: Option<&str>) -> Config {
+    let mut c = config(repo, branch, None);
+    c.file = FileConfig::parse(
+        r##"
+[[services]]
+host = "*.code.example.com"
+repo = "https://{host}/{user}/{repo}"
+branch = "https://{host}/{user}/{repo}/-/{branch}"
+commit = "https://{host}/{user}/{repo}/-/commit/{hash}"
+file = "https://{host}/{user}/{repo}/-/blob/{hash}/{path}"
+blame = "https://{host}/{user}/{repo}/-/blame/{hash}/{path}"
+line = "#L{line_start}"
+line_range = "#L{line_start}-{line_end}"
+diff_two_dots = "https://{host}/{user}/{repo}/-/diff/{lhs}/{rhs}"
+issue = "https://{host}/{user}/{repo}/-/issues/{number}"
+tag = "https://{host}/{user}/{repo}/-/tags/{tag}"
+website = "https://{host}/{user}/{repo}/-/{unknown}"
+"##,
+    )
+    .unwrap();
+    c
+}
+
+#[test]
+fn templated_pages() {
+    let b = "https://git.code.example.com/group/sub/repo";
+    for (page, branch, expected) in &[
+        (OPEN, None, b.to_string()),
+        (OPEN, Some("dev"), format!("{}/-/dev", b)),
+        (
+            Page::Commit {
+                hash: "0123456".to_string(),
+            },
+            None,
+            format!("{}/-/commit/0123456", b),
+        ),
+        (
+            Page::Tag {
+                tagname: "v1.0".to_string(),
+                commit: "0123456".to_string(),
+            },
+            None,
+            format!("{}/-/tags/v1.0", b),
+        ),
+        (
+            Page::Diff {
+                lhs: "0123456".to_string(),
+                rhs: "789abcd".to_string(),
+                op: DiffOp::TwoDots,
+            },
+            None,
+            format!("{}/-/diff/0123456/789abcd", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "0123456".to_string(),
+                line: None,
+                blame: false,
+                is_dir: true,
+            },
+            None,
+            format!("{}/-/blob/0123456/src/main.rs", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::At(3)),
+                blame: false,
+                is_dir: false,
+            },
+            None,
+            format!("{}/-/blob/0123456/src/main.rs#L3", b),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "src/main.rs".to_string(),
+                hash: "0123456".to_string(),
+                line: Some(Line::Range(3, 5)),
+                blame: true,
+                is_dir: false,
+            },
+            None,
+            format!("{}/-/blame/0123456/src/main.rs#L3-5", b),
+        ),
+        (Page::Issue { number: 8 }, None, format!("{}/-/issues/8", b)),
+    ] {
+        let c = config_with_templates(
+            "ssh://git@git.code.example.com:22/group/sub/repo.git",
+            *branch,
+        );
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn templated_page_errors() {
+    let c = config_with_templates("https://git.code.example.com/user/repo.git", None);
+    let page = Page::Diff {
+        lhs: "0123456".to_string(),
+        rhs: "789abcd".to_string(),
+        op: DiffOp::ThreeDots,
+    };
+    match build_page_url(&page, &c).unwrap_err().kind() {
+        ErrorKind::UrlTemplateNotFound { template, .. } => assert_eq!(*template, "diff_three_dots"),
+        e => panic!("Unexpected error: {:?}", e),
+    }
+    match build_page_url(&OPEN_WEBSITE, &c).unwrap_err().kind() {
+        ErrorKind::BrokenUrlTemplate { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+
+    let c = config_with_templates("https://code.example.com/user/repo.git", None);
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::UnknownHostingService { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn templated_page_without_user() {
+    let c = config_with_templates("https://git.code.example.com/repo.git", None);
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::NoUserInPath { path } => assert_eq!(path, "repo"),
+        e => panic!("Unexpected error: {:?}", e),
+    }
+
+    let mut c = config("https://code.internal/repo.git", None, None);
+    c.file = FileConfig::parse(
+        r#"
+[[services]]
+host = "code.internal"
+repo = "https://{host}/{repo}"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://code.internal/repo"
+    );
+}
+
+#[test]
+fn cgit_and_gitweb_in_config_file() {
+    let file = FileConfig::parse(
+        r#"
+[[hosts]]
+host = "git.example.com"
+kind = "cgit"
+path_prefix = "cgit"
+
+[[hosts]]
+host = "code.example.com"
+kind = "gitweb"
+scheme = "http"
+port = 8080
+path_prefix = "gitweb"
+"#,
+    )
+    .unwrap();
+
+    for (repo, expected) in &[
+        (
+            "https://git.example.com/cgit/pub/repo.git",
+            "https://git.example.com/cgit/pub/repo.git/commit/?id=0123456",
+        ),
+        (
+            "git://git.example.com/pub/repo.git",
+            "https://git.example.com/cgit/pub/repo.git/commit/?id=0123456",
+        ),
+        (
+            "ssh://git@code.example.com:22/repo.git",
+            "http://code.example.com:8080/gitweb/?p=repo.git;a=commit;h=0123456",
+        ),
+    ] {
+        let mut c = config(repo, None, None);
+        c.file = file.clone();
+        let page = Page::Commit {
+            hash: "0123456".to_string(),
+        };
+        assert_eq!(&build_page_url(&page, &c).unwrap(), expected);
+    }
+
+    let mut c = config("https://git.example.com/cgit/pub/repo.git", None, None);
+    c.file = file;
+    match build_page_url(&OPEN_PR, &c).unwrap_err().kind() {
+        ErrorKind::PullReqNotSupported { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn multiple_hosts_in_config_file() {
+    let file = FileConfig::parse(
+        r#"
+[[hosts]]
+host = "ghe1.example.com"
+kind = "github"
+
+[[hosts]]
+host = "ghe2.example.com"
+kind = "github"
+port = 8443
+
+[[hosts]]
+host = "gitea.example.com"
+kind = "gitlab"
+
+[[hosts]]
+host = "code.example.com"
+kind = "gitlab"
+scheme = "http"
+"#,
+    )
+    .unwrap();
+
+    for (repo, expected) in &[
+        (
+            "ssh://git@ghe1.example.com:22/user/repo.git",
+            "https://ghe1.example.com/user/repo/commit/0123456",
+        ),
+        (
+            "https://ghe2.example.com/user/repo.git",
+            "https://ghe2.example.com:8443/user/repo/commit/0123456",
+        ),
+        (
+            "https://gitea.example.com/group/sub/repo.git",
+            "https://gitea.example.com/group/sub/repo/commit/0123456",
+        ),
+        (
+            "ssh://git@code.example.com:22/user/repo.git",
+            "http://code.example.com/user/repo/commit/0123456",
+        ),
+    ] {
+        let mut c = config(repo, None, None);
+        c.file = file.clone();
+        let page = Page::Commit {
+            hash: "0123456".to_string(),
+        };
+        assert_eq!(&build_page_url(&page, &c).unwrap(), expected);
+    }
+
+    let mut c = config("https://gitea.example.com/user/repo.git", None, None);
+    c.file = file;
+    let page = Page::Diff {
+        lhs: "0123456".to_string(),
+        rhs: "789abcd".to_string(),
+        op: DiffOp::TwoDots,
+    };
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://gitea.example.com/user/repo/compare/0123456...789abcd?straight=true",
+    );
+}
+
+#[test]
+fn broken_repo_url() {
+    let env = &empty_env();
+    for &url in &[
+        "https://foo@/foo.bar", // empty host
+        "https://foo bar",      // invalid domain character
+    ] {
+        let c = config(url, None, Some(env.clone()));
+        match build_page_url(&OPEN, &c).unwrap_err().kind() {
+            ErrorKind::BrokenUrl { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn issue_number_url() {
+    let p = Page::Issue { number: 123 };
+    for &(repo, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            "https://github.com/user/repo/issues/123",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            "https://bitbucket.org/user/repo/issues/123",
+        ),
+        (
+            "https://github.somewhere.com/user/repo.git",
+            "https://github.somewhere.com/user/repo/issues/123",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            "https://gitlab.com/user/repo/issues/123",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            "https://dev.azure.com/team/repo/_workitems/edit/123",
+        ),
+        (
+            "https://codeberg.org/user/repo.git",
+            "https://codeberg.org/user/repo/issues/123",
+        ),
+        (
+            "https://git.sr.ht/~user/repo",
+            "https://todo.sr.ht/~user/repo/123",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn unknown_github_enterprise_url() {
+    let mut env = empty_env();
+    env.ghe_url_host = Some("github-yourcompany.com".to_string());
+    let c = config(
+        "https://github-othercompany.com/foo/bar.git",
+        None,
+        Some(env),
+    );
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
+        err => panic!("Unexpected error: {}", err),
+    }
+
+    let mut c = config_for_pr(None, "https://github-othercompany.com/foo/bar.git", None);
+    c.env.ghe_url_host = Some("github-yourcompany.com".to_string());
+    match build_page_url(&OPEN_PR, &c).unwrap_err().kind() {
+        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
+        err => panic!("Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn website_github_pages() {
+    let mut env = empty_env();
+    env.github_token = skip_if_no_token!();
+    env.https_proxy = https_proxy();
+    let env = env;
+    let testcases = &[
+        (
+            "https://github.com/rhysd/git-brws.git", // With gh-pages
+            "https://rhysd.github.io/git-brws/",
+        ),
+        (
+            "https://github.com/rhysd/dogfiles", // Without gh-pages, but with homepage
+            "https://rhysd.github.io",
+        ),
+        (
+            "https://github.com/rhysd/filter-with-state", // Without homepage
+            "https://rhysd.github.io/filter-with-state",
         ),
     ];
     for (url, expected) in testcases {
@@ -692,20 +2186,385 @@ fn pull_request_page_url_without_branch_outside_git_repo() {
 }
 
 #[test]
-fn pull_request_unsupported_services() {
-    let urls = &[
-        "https://gitlab.com/foo/bar.git",
-        "https://gitlab.yourcompany.com/foo/bar.git",
-        "https://bitbucket.org/foo/bar.git",
-        "ssh://git@gitlab.com:22/foo/bar.git",
-        "ssh://git@gitlab.yourcompany.com:22/foo/bar.git",
-        "ssh://git@bitbucket.org:22/foo/bar.git",
-    ];
-    for url in urls {
-        let cfg = config_for_pr(None, url, None);
-        match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
-            ErrorKind::PullReqNotSupported { .. } => { /* OK */ }
-            err => panic!("Unexpected error for URL {}: {}", url, err),
+fn pull_request_bitbucket_request_failure() {
+    let mut cfg = config_for_pr(None, "https://bitbucket.org/foo/bar.git", Some("topic"));
+    cfg.env.https_proxy = Some("https://unknown.proxy.example.com".to_string());
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* OK */ }
+        err => panic!("Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn pull_request_azure_devops_page_urls() {
+    let repo_url = "https://dev.azure.com/org/project/_git/repo";
+    assert_eq!(
+        azure_devops_pr_page_url(repo_url, AzureDevOpsPage::Existing { id: 42 }),
+        "https://dev.azure.com/org/project/_git/repo/pullrequest/42",
+    );
+    let new = AzureDevOpsPage::New {
+        branch: "topic".to_string(),
+        target: Some("main".to_string()),
+    };
+    assert_eq!(
+        azure_devops_pr_page_url(repo_url, new),
+        "https://dev.azure.com/org/project/_git/repo/pullrequestcreate?sourceRef=topic&targetRef=main",
+    );
+    let new = AzureDevOpsPage::New {
+        branch: "topic".to_string(),
+        target: None,
+    };
+    assert_eq!(
+        azure_devops_pr_page_url(repo_url, new),
+        "https://dev.azure.com/org/project/_git/repo/pullrequestcreate?sourceRef=topic",
+    );
+}
+
+#[test]
+fn pull_request_azure_devops_request_failure_opens_create_page() {
+    let mut cfg = config_for_pr(
+        None,
+        "https://dev.azure.com/team/repo/_git/repo",
+        Some("topic"),
+    );
+    cfg.env.https_proxy = Some("https://unknown.proxy.example.com".to_string());
+    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
+    assert!(
+        url.starts_with(
+            "https://dev.azure.com/team/repo/_git/repo/pullrequestcreate?sourceRef=topic"
+        ),
+        "{}",
+        url,
+    );
+}
+
+#[test]
+fn pull_request_gitlab_request_failure() {
+    let cfg = config_for_pr(
+        None,
+        "ssh://git@gitlab.unknown.endpoint.example.com:22/group/sub/repo.git",
+        Some("topic"),
+    );
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* OK */ }
+        err => panic!("Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn pull_request_gitlab_page_urls() {
+    let existing = GitLabPage::Existing {
+        url: "https://gitlab.com/group/repo/-/merge_requests/8".to_string(),
+    };
+    assert_eq!(
+        gitlab_mr_page_url("https://gitlab.com", "group/repo", existing),
+        "https://gitlab.com/group/repo/-/merge_requests/8",
+    );
+    let new = GitLabPage::New {
+        branch: "topic".to_string(),
+        target_project_id: None,
+    };
+    assert_eq!(
+        gitlab_mr_page_url("http://gitlab.example.com:8080", "group/sub/repo", new),
+        "http://gitlab.example.com:8080/group/sub/repo/-/merge_requests/new?merge_request[source_branch]=topic",
+    );
+    let new_at_parent = GitLabPage::New {
+        branch: "topic".to_string(),
+        target_project_id: Some(42),
+    };
+    assert_eq!(
+        gitlab_mr_page_url("https://gitlab.com", "me/repo", new_at_parent),
+        "https://gitlab.com/me/repo/-/merge_requests/new?merge_request[source_branch]=topic&merge_request[target_project_id]=42",
+    );
+}
+
+#[test]
+fn pull_request_bitbucket_page_urls() {
+    let existing = PrPage::Existing {
+        url: "https://bitbucket.org/foo/bar/pull-requests/3".to_string(),
+    };
+    assert_eq!(
+        bitbucket_pr_page_url(existing),
+        "https://bitbucket.org/foo/bar/pull-requests/3",
+    );
+    let new = PrPage::New {
+        author: "foo".into(),
+        repo: "bar".into(),
+        branch: "topic".to_string(),
+    };
+    assert_eq!(
+        bitbucket_pr_page_url(new),
+        "https://bitbucket.org/foo/bar/pull-requests/new?source=topic",
+    );
+}
+
+#[test]
+fn pull_request_bitbucket_server_page_urls() {
+    let base = "https://bitbucket.example.com/projects/PROJ/repos/repo";
+    let existing = PrPage::Existing {
+        url: format!("{}/pull-requests/5", base),
+    };
+    assert_eq!(
+        bitbucket_server_pr_page_url(base, existing),
+        "https://bitbucket.example.com/projects/PROJ/repos/repo/pull-requests/5",
+    );
+    let new = PrPage::New {
+        author: "PROJ".into(),
+        repo: "repo".into(),
+        branch: "topic".to_string(),
+    };
+    assert_eq!(
+        bitbucket_server_pr_page_url(base, new),
+        "https://bitbucket.example.com/projects/PROJ/repos/repo/pull-requests?create&sourceBranch=refs/heads/topic",
+    );
+}
+
+#[test]
+fn github_enterprise_cloud_tenant() {
+    let c = config("ssh://git@octocorp.ghe.com:22/org/repo.git", None, None);
+    let page = Page::Commit {
+        hash: "0123456".to_string(),
+    };
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://octocorp.ghe.com/org/repo/commit/0123456",
+    );
+
+    let cfg = config_for_pr(None, "https://octocorp.ghe.com/org/repo.git", None);
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::GheTokenRequired => { /* OK */ }
+        err => panic!("Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn hosts_with_path_prefix() {
+    let file = FileConfig::parse(
+        r#"
+[[hosts]]
+host = "gitlab.example.com"
+kind = "gitlab"
+path_prefix = "gitlab"
+
+[[hosts]]
+host = "gitea.example.com"
+kind = "gitea"
+path_prefix = "/git/"
+"#,
+    )
+    .unwrap();
+
+    let commit = Page::Commit {
+        hash: "0123456".to_string(),
+    };
+    let file_page = Page::FilePath {
+        relative_path: "src/main.rs".to_string(),
+        hash: "0123456".to_string(),
+        line: None,
+        blame: false,
+        is_dir: false,
+    };
+    for (repo, page, expected) in &[
+        (
+            "https://gitlab.example.com/gitlab/group/repo.git",
+            &OPEN,
+            "https://gitlab.example.com/gitlab/group/repo",
+        ),
+        (
+            "ssh://git@gitlab.example.com:22/group/repo.git",
+            &commit,
+            "https://gitlab.example.com/gitlab/group/repo/commit/0123456",
+        ),
+        (
+            "ssh://git@gitlab.example.com:22/gitlab/group/repo.git",
+            &file_page,
+            "https://gitlab.example.com/gitlab/group/repo/blob/0123456/src/main.rs",
+        ),
+        (
+            "https://gitea.example.com/git/user/repo.git",
+            &OPEN,
+            "https://gitea.example.com/git/user/repo",
+        ),
+        (
+            "ssh://git@gitea.example.com:22/user/repo.git",
+            &commit,
+            "https://gitea.example.com/git/user/repo/commit/0123456",
+        ),
+    ] {
+        let mut c = config(repo, None, None);
+        c.file = file.clone();
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn detected_services_in_cache() {
+    let path = std::env::temp_dir().join("git-brws-test-detected-services.json");
+    std::fs::write(
+        &path,
+        r#"{"hosts": {"code.internal.example.com": "gitlab", "scm.internal.example.com": "bitbucket_server"}}"#,
+    )
+    .unwrap();
+    let mut env = empty_env();
+    env.cache_file = Some(path.to_str().unwrap().to_string());
+    env.probe_unknown_hosts = true;
+
+    for &(repo, expected) in &[
+        (
+            "https://code.internal.example.com/group/repo.git",
+            "https://code.internal.example.com/group/repo",
+        ),
+        (
+            "http://code.internal.example.com:8080/group/repo.git",
+            "http://code.internal.example.com:8080/group/repo",
+        ),
+        (
+            "https://scm.internal.example.com/scm/proj/repo.git",
+            "https://scm.internal.example.com/projects/PROJ/repos/repo/browse",
+        ),
+    ] {
+        let c = config(repo, None, Some(env.clone()));
+        assert_eq!(build_page_url(&OPEN, &c).unwrap(), expected);
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn gist_pages() {
+    let base = "https://gist.github.com/user/0123456789abcdef";
+    let hash = "561848bad7164d7568658456088b107ec9efd9f3";
+    for (page, expected) in [
+        (OPEN, base.to_string()),
+        (
+            Page::Commit {
+                hash: hash.to_string(),
+            },
+            format!("{}/{}", base, hash),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "Hello World.rs".to_string(),
+                hash: "master".to_string(),
+                line: None,
+                blame: false,
+                is_dir: false,
+            },
+            format!("{}#file-hello-world-rs", base),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "hello_world.rb".to_string(),
+                hash: hash.to_string(),
+                line: Some(Line::At(10)),
+                blame: false,
+                is_dir: false,
+            },
+            format!("{}/{}#file-hello_world-rb-L10", base, hash),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "hello_world.rb".to_string(),
+                hash: "master".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+                is_dir: false,
+            },
+            format!("{}#file-hello_world-rb-L10-L20", base),
+        ),
+    ] {
+        let c = config(
+            "https://gist.github.com/user/0123456789abcdef.git",
+            None,
+            None,
+        );
+        assert_eq!(build_page_url(&page, &c).unwrap(), expected);
+    }
+
+    // Owner cannot be fetched without network. The URL without owner is redirected.
+    let mut c = config(
+        "ssh://git@gist.github.com:22/0123456789abcdef.git",
+        None,
+        None,
+    );
+    c.env.https_proxy = Some("https://unknown.proxy.example.com".to_string());
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://gist.github.com/0123456789abcdef",
+    );
+
+    let c = config(
+        "https://gist.github.com/user/0123456789abcdef.git",
+        None,
+        None,
+    );
+    match build_page_url(&Page::Issue { number: 1 }, &c)
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::GistNotSupported { .. } => { /* ok */ }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn github_wiki_pages() {
+    let base = "https://github.com/user/repo/wiki";
+    let hash = "561848bad7164d7568658456088b107ec9efd9f3";
+    for (page, expected) in [
+        (OPEN, base.to_string()),
+        (
+            Page::Commit {
+                hash: hash.to_string(),
+            },
+            format!("{}/_compare/{}", base, hash),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "Getting-Started.md".to_string(),
+                hash: "master".to_string(),
+                line: Some(Line::At(3)),
+                blame: false,
+                is_dir: false,
+            },
+            format!("{}/Getting-Started", base),
+        ),
+        (
+            Page::FilePath {
+                relative_path: "docs/Home.asciidoc".to_string(),
+                hash: hash.to_string(),
+                line: None,
+                blame: false,
+                is_dir: false,
+            },
+            format!("{}/Home/{}", base, hash),
+        ),
+    ] {
+        let c = config("https://github.com/user/repo.wiki.git", None, None);
+        assert_eq!(build_page_url(&page, &c).unwrap(), expected);
+    }
+
+    let c = config("https://github.com/user/repo.wiki.git", None, None);
+    for page in &[
+        Page::FilePath {
+            relative_path: "images/logo.png".to_string(),
+            hash: "master".to_string(),
+            line: None,
+            blame: false,
+            is_dir: false,
+        },
+        Page::FilePath {
+            relative_path: "Home.md".to_string(),
+            hash: "master".to_string(),
+            line: None,
+            blame: true,
+            is_dir: false,
+        },
+        Page::Issue { number: 1 },
+    ] {
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::GitHubWikiNotSupported { .. } => { /* ok */ }
+            e => panic!("Unexpected error: {:?}", e),
         }
     }
 }
@@ -822,6 +2681,7 @@ fn slash_in_github_user_name() {
         "https://github.com/foo/bar/repo.git",
         "https://github.somewhere.com/foo/bar/repo.git",
         "https://bitbucket.org/foo/bar/repo.git",
+        "https://codeberg.org/foo/bar/repo.git",
     ] {
         let c = config(repo, None, None);
         let err = build_page_url(&OPEN, &c).unwrap_err();
@@ -0,0 +1,209 @@
+use crate::ssh_config::SshConfig;
+use crate::test::helper::empty_env;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn resolve_host_alias() {
+    let config = SshConfig::parse(
+        r#"
+# Comment
+Host github-work
+    HostName github.com
+    IdentityFile ~/.ssh/id_work
+
+Host gitlab-* !gitlab-ignored
+  HostName=gitlab.example.com
+  Port 2222
+
+Host *.corp
+    HostName %h.example.com
+
+Match host foo
+    HostName match.example.com
+
+Host *
+    Port 10022
+    HostName never-used.example.com
+"#,
+        Path::new("/path/to/unknown"),
+    );
+
+    let (host, port) = config.resolve("github-work");
+    assert_eq!(host, "github.com");
+    assert_eq!(port, Some(10022));
+
+    let (host, port) = config.resolve("gitlab-home");
+    assert_eq!(host, "gitlab.example.com");
+    assert_eq!(port, Some(2222));
+
+    let (host, port) = config.resolve("gitlab-ignored");
+    assert_eq!(host, "never-used.example.com");
+    assert_eq!(port, Some(10022));
+
+    let (host, _) = config.resolve("git.corp");
+    assert_eq!(host, "git.corp.example.com");
+
+    let (host, _) = config.resolve("foo");
+    assert_eq!(host, "never-used.example.com");
+}
+
+#[test]
+fn resolve_host_without_config() {
+    let config = SshConfig::parse("", Path::new("/path/to/unknown"));
+    assert_eq!(
+        config.resolve("github.com"),
+        ("github.com".to_string(), None)
+    );
+
+    let mut env = empty_env();
+    env.ssh_config_file = Some("/path/to/unknown/config".to_string());
+    let config = SshConfig::load(&env);
+    assert_eq!(
+        config.resolve("github.com"),
+        ("github.com".to_string(), None)
+    );
+}
+
+#[test]
+fn resolve_host_alias_in_included_files() {
+    let dir = env::temp_dir().join("git-brws-test-ssh-config");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("config.d")).unwrap();
+    fs::write(
+        dir.join("config"),
+        "Include config.d/*.conf\nHost work\n  Include work\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("config.d").join("a.conf"),
+        "Host github-work\n  HostName github.com\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("config.d").join("b.conf"),
+        "Host github-work\n  HostName wrong.example.com\n  Port 443\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("config.d").join("ignored"),
+        "Host *\n  HostName wrong.example.com\n",
+    )
+    .unwrap();
+    // Lines before the first 'Host' belong to the block containing 'Include'
+    fs::write(dir.join("work"), "HostName gitlab.example.com\n").unwrap();
+
+    let mut env = empty_env();
+    env.ssh_config_file = Some(dir.join("config").to_str().unwrap().to_string());
+    let config = SshConfig::load(&env);
+
+    assert_eq!(
+        config.resolve("github-work"),
+        ("github.com".to_string(), Some(443)),
+    );
+    assert_eq!(
+        config.resolve("work"),
+        ("gitlab.example.com".to_string(), None),
+    );
+    assert_eq!(config.resolve("other"), ("other".to_string(), None));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn recursive_include() {
+    let dir = env::temp_dir().join("git-brws-test-ssh-config-recursive");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("config"),
+        "Include config\nHost foo\n  HostName foo.example.com\n",
+    )
+    .unwrap();
+
+    let mut env = empty_env();
+    env.ssh_config_file = Some(dir.join("config").to_str().unwrap().to_string());
+    let config = SshConfig::load(&env);
+    assert_eq!(config.resolve("foo").0, "foo.example.com");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn resolve_host_alias_of_url() {
+    let config = SshConfig::parse(
+        r#"
+Host github-work
+    HostName github.com
+    Port 10022
+
+Host github.com
+    HostName ssh.github.com
+    Port 443
+"#,
+        Path::new("/path/to/unknown"),
+    );
+
+    for (url, expected) in &[
+        (
+            "ssh://git@github-work:22/org/repo.git",
+            "ssh://git@github.com:22/org/repo.git",
+        ),
+        (
+            "ssh://git@github-work/org/repo.git",
+            "ssh://git@github.com:10022/org/repo.git",
+        ),
+        // Port in the URL is prioritized and SSH over HTTPS host is mapped to the original host
+        (
+            "ssh://git@github.com:22/org/repo.git",
+            "ssh://git@github.com:22/org/repo.git",
+        ),
+        (
+            "ssh://git@github.com/org/repo.git",
+            "ssh://git@github.com:443/org/repo.git",
+        ),
+        (
+            "https://github-work/org/repo.git",
+            "https://github-work/org/repo.git",
+        ),
+        (
+            "ssh://git@example.com:22/org/repo.git",
+            "ssh://git@example.com:22/org/repo.git",
+        ),
+    ] {
+        assert_eq!(&config.resolve_url(url.to_string(), false), expected);
+    }
+}
+
+#[test]
+fn implicit_port_is_overridden_by_config() {
+    let config = SshConfig::parse(
+        r#"
+Host git.example.com
+    Port 2222
+"#,
+        Path::new("/path/to/unknown"),
+    );
+
+    // Port inserted for scp-like syntax is overridden by 'Port' in the config
+    assert_eq!(
+        &config.resolve_url(
+            "ssh://git@git.example.com:22/org/repo.git".to_string(),
+            true
+        ),
+        "ssh://git@git.example.com:2222/org/repo.git",
+    );
+    assert_eq!(
+        &config.resolve_url(
+            "ssh://git@git.example.com:22/org/repo.git".to_string(),
+            false
+        ),
+        "ssh://git@git.example.com:22/org/repo.git",
+    );
+    // Implicit port remains when no 'Port' is configured for the host
+    assert_eq!(
+        &config.resolve_url("ssh://git@github.com:22/org/repo.git".to_string(), true),
+        "ssh://git@github.com:22/org/repo.git",
+    );
+}
@@ -1,4 +1,4 @@
-use crate::config::{Config, EnvConfig};
+use crate::config::{Config, EnvConfig, FileConfig};
 use crate::error::ErrorKind;
 use crate::test::helper::empty_env;
 use crate::url;
@@ -42,6 +42,7 @@ fn smoke() {
         blame: false,
         remote: None,
         env: empty_env(),
+        file: FileConfig::default(),
     };
     match url::build_url(&c) {
         Ok(u) => assert_eq!(